@@ -0,0 +1,223 @@
+//! Settings from `lafmt.toml`, looked up from every formatted file towards the root.
+
+use crate::format::{Case, CommaStyle, FormatOptions};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fs, io};
+
+/// File names looked for in every directory, in order of preference.
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["lafmt.toml", ".lafmt.toml"];
+
+/// Settings from a config file or from the command line. Unset ones keep the value they had.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Config {
+    pub indent_width: Option<usize>,
+    pub max_width: Option<usize>,
+    pub keyword_case: Option<Case>,
+    pub identifier_case: Option<Case>,
+    pub comma_style: Option<CommaStyle>,
+    pub align: Option<bool>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    UnknownKey(String),
+    InvalidValue { key: String, message: String },
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(error: toml::de::Error) -> Self {
+        ConfigError::Toml(error)
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "{}", error),
+            ConfigError::Toml(error) => write!(f, "{}", error.message()),
+            ConfigError::UnknownKey(key) => write!(f, "unknown setting '{}'", key),
+            ConfigError::InvalidValue { key, message } => {
+                write!(f, "invalid value for '{}': {}", key, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(error) => Some(error),
+            ConfigError::Toml(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Config, ConfigError> {
+        let table: toml::Table = text.parse()?;
+        let mut config = Config::default();
+        for (key, value) in &table {
+            match key.as_str() {
+                "indent_width" => config.indent_width = Some(integer(key, value)?),
+                "max_width" => config.max_width = Some(integer(key, value)?),
+                "keyword_case" => config.keyword_case = Some(choice(key, value)?),
+                "identifier_case" => config.identifier_case = Some(choice(key, value)?),
+                "comma_style" => config.comma_style = Some(choice(key, value)?),
+                "align" => match value.as_bool() {
+                    Some(align) => config.align = Some(align),
+                    None => return Err(invalid_value(key, "true or false", value)),
+                },
+                _ => return Err(ConfigError::UnknownKey(key.to_string())),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        Config::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Finds the config file nearest to `path`, looking next to it and then in every parent
+    /// directory.
+    pub fn find(path: &Path) -> Option<PathBuf> {
+        path.ancestors()
+            .flat_map(|dir| CONFIG_FILE_NAMES.iter().map(move |name| dir.join(name)))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Overrides `opts` with the settings present here.
+    pub fn apply(&self, opts: &mut FormatOptions) {
+        if let Some(indent_width) = self.indent_width {
+            opts.indent_width = indent_width;
+        }
+        if let Some(max_width) = self.max_width {
+            opts.max_width = max_width;
+        }
+        if let Some(keyword_case) = self.keyword_case {
+            opts.keyword_case = keyword_case;
+        }
+        if let Some(identifier_case) = self.identifier_case {
+            opts.identifier_case = identifier_case;
+        }
+        if let Some(comma_style) = self.comma_style {
+            opts.comma_style = comma_style;
+        }
+        if let Some(align) = self.align {
+            opts.align = align;
+        }
+    }
+}
+
+fn integer(key: &str, value: &toml::Value) -> Result<usize, ConfigError> {
+    value
+        .as_integer()
+        .and_then(|integer| usize::try_from(integer).ok())
+        .filter(|integer| *integer > 0)
+        .ok_or_else(|| invalid_value(key, "a positive integer", value))
+}
+
+fn choice<T: FromStr<Err = String>>(key: &str, value: &toml::Value) -> Result<T, ConfigError> {
+    match value.as_str() {
+        Some(text) => text.parse().map_err(|message| ConfigError::InvalidValue {
+            key: key.to_string(),
+            message,
+        }),
+        None => Err(invalid_value(key, "a string", value)),
+    }
+}
+
+fn invalid_value(key: &str, expected: &str, value: &toml::Value) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: key.to_string(),
+        message: format!("expected {}, found {}", expected, value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Config, ConfigError};
+    use crate::format::{Case, CommaStyle, FormatOptions};
+    use std::fs;
+
+    #[test]
+    pub fn test_parse_config() {
+        let config = Config::parse(
+            "indent_width = 2\n\
+             max_width = 80\n\
+             keyword_case = \"lower\"\n\
+             identifier_case = \"upper\"\n\
+             comma_style = \"leading\"\n\
+             align = false\n",
+        )
+        .unwrap();
+
+        let mut opts = FormatOptions::default();
+        config.apply(&mut opts);
+        assert_eq!(2, opts.indent_width);
+        assert_eq!(80, opts.max_width);
+        assert_eq!(Case::Lower, opts.keyword_case);
+        assert_eq!(Case::Upper, opts.identifier_case);
+        assert_eq!(CommaStyle::Leading, opts.comma_style);
+        assert!(!opts.align);
+
+        let mut opts = FormatOptions::default();
+        Config::parse("max_width = 120").unwrap().apply(&mut opts);
+        assert_eq!(120, opts.max_width);
+        assert_eq!(4, opts.indent_width);
+    }
+
+    #[test]
+    pub fn test_config_errors() {
+        assert!(matches!(
+            Config::parse("indent = 2"),
+            Err(ConfigError::UnknownKey(key)) if key == "indent"
+        ));
+        assert_eq!(
+            "invalid value for 'indent_width': expected a positive integer, found -1",
+            Config::parse("indent_width = -1").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "invalid value for 'max_width': expected a positive integer, found 0",
+            Config::parse("max_width = 0").unwrap_err().to_string()
+        );
+        assert!(Config::parse("indent_width = 0").is_err());
+        assert_eq!(
+            "invalid value for 'keyword_case': expected upper, lower or preserve, found 'title'",
+            Config::parse("keyword_case = \"title\"")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(matches!(
+            Config::parse("align = "),
+            Err(ConfigError::Toml(_))
+        ));
+    }
+
+    #[test]
+    pub fn test_find_nearest_config() {
+        let root = std::env::temp_dir().join(format!("lafmt-config-{}", std::process::id()));
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(".lafmt.toml"), "").unwrap();
+        fs::write(root.join("a").join("lafmt.toml"), "").unwrap();
+        fs::write(root.join("a").join(".lafmt.toml"), "").unwrap();
+
+        let found_nested = Config::find(&nested.join("query.sql"));
+        let found_root = Config::find(&root.join("query.sql"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(Some(root.join("a").join("lafmt.toml")), found_nested);
+        assert_eq!(Some(root.join(".lafmt.toml")), found_root);
+    }
+}
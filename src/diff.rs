@@ -0,0 +1,313 @@
+//! Line based unified diff, used to show what the formatter would change.
+
+use std::cmp::min;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Renders the difference between `old` and `new` in unified format with `context` lines
+/// around every change. Returns an empty string when both texts are equal.
+pub fn unified_diff(
+    old: &str,
+    new: &str,
+    old_name: &str,
+    new_name: &str,
+    context: usize,
+) -> String {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+
+    let mut ops = vec![];
+    diff_lines(&old_lines, &new_lines, &mut ops);
+    if ops.iter().all(|(edit, _)| *edit == Edit::Equal) {
+        return String::new();
+    }
+
+    // Line positions in both texts before every op, used for the hunk headers.
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for (edit, _) in &ops {
+        positions.push((old_pos, new_pos));
+        match edit {
+            Edit::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            Edit::Delete => old_pos += 1,
+            Edit::Insert => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, stop) in hunks(&ops, context) {
+        let (old_start, new_start) = positions[start];
+        let (old_stop, new_stop) = positions[stop];
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_stop - old_start),
+            hunk_range(new_start, new_stop - new_start)
+        ));
+        for (edit, line) in &ops[start..stop] {
+            out.push(match edit {
+                Edit::Equal => ' ',
+                Edit::Delete => '-',
+                Edit::Insert => '+',
+            });
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    out
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        // An empty range points at the line before the hunk.
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+/// Groups changed ops into `(start, stop)` op ranges, merging changes whose context overlaps.
+fn hunks(ops: &[(Edit, &str)], context: usize) -> Vec<(usize, usize)> {
+    let mut hunks = vec![];
+    let mut i = 0;
+    while i < ops.len() {
+        if ops[i].0 == Edit::Equal {
+            i += 1;
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let mut end = i;
+        loop {
+            while end < ops.len() && ops[end].0 != Edit::Equal {
+                end += 1;
+            }
+            let mut next = end;
+            while next < ops.len() && ops[next].0 == Edit::Equal {
+                next += 1;
+            }
+            if next < ops.len() && next - end <= 2 * context {
+                end = next;
+            } else {
+                break;
+            }
+        }
+        let stop = min(end + context, ops.len());
+        hunks.push((start, stop));
+        i = stop;
+    }
+    hunks
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str], ops: &mut Vec<(Edit, &'a str)>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    ops.extend(old[..prefix].iter().map(|line| (Edit::Equal, *line)));
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    if old_mid.is_empty() || new_mid.is_empty() {
+        ops.extend(old_mid.iter().map(|line| (Edit::Delete, *line)));
+        ops.extend(new_mid.iter().map(|line| (Edit::Insert, *line)));
+    } else {
+        bisect(old_mid, new_mid, ops);
+    }
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| (Edit::Equal, *line)),
+    );
+}
+
+/// Finds the middle snake of the shortest edit script (Myers, 1986) and diffs both halves,
+/// which keeps memory linear in the input size.
+fn bisect<'a>(old: &[&'a str], new: &[&'a str], ops: &mut Vec<(Edit, &'a str)>) {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d;
+    let len = 2 * max_d + 2;
+    let mut forward = vec![-1isize; len as usize];
+    let mut backward = vec![-1isize; len as usize];
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+    let delta = n - m;
+    // With an odd delta the paths can only meet while extending the forward one.
+    let front = delta % 2 != 0;
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        let mut k1 = -d + k1_start;
+        while k1 <= d - k1_end {
+            let k1_offset = (offset + k1) as usize;
+            let mut x1 = if k1 == -d || (k1 != d && forward[k1_offset - 1] < forward[k1_offset + 1])
+            {
+                forward[k1_offset + 1]
+            } else {
+                forward[k1_offset - 1] + 1
+            };
+            let mut y1 = x1 - k1;
+            while x1 < n && y1 < m && old[x1 as usize] == new[y1 as usize] {
+                x1 += 1;
+                y1 += 1;
+            }
+            forward[k1_offset] = x1;
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if front {
+                let k2_offset = offset + delta - k1;
+                if (0..len).contains(&k2_offset) && backward[k2_offset as usize] != -1 {
+                    let x2 = n - backward[k2_offset as usize];
+                    if x1 >= x2 {
+                        return split(old, new, x1 as usize, y1 as usize, ops);
+                    }
+                }
+            }
+            k1 += 2;
+        }
+
+        let mut k2 = -d + k2_start;
+        while k2 <= d - k2_end {
+            let k2_offset = (offset + k2) as usize;
+            let mut x2 =
+                if k2 == -d || (k2 != d && backward[k2_offset - 1] < backward[k2_offset + 1]) {
+                    backward[k2_offset + 1]
+                } else {
+                    backward[k2_offset - 1] + 1
+                };
+            let mut y2 = x2 - k2;
+            while x2 < n && y2 < m && old[(n - x2 - 1) as usize] == new[(m - y2 - 1) as usize] {
+                x2 += 1;
+                y2 += 1;
+            }
+            backward[k2_offset] = x2;
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !front {
+                let k1_offset = offset + delta - k2;
+                if (0..len).contains(&k1_offset) && forward[k1_offset as usize] != -1 {
+                    let x1 = forward[k1_offset as usize];
+                    let y1 = offset + x1 - k1_offset;
+                    if x1 >= n - x2 {
+                        return split(old, new, x1 as usize, y1 as usize, ops);
+                    }
+                }
+            }
+            k2 += 2;
+        }
+    }
+
+    // Nothing in common.
+    ops.extend(old.iter().map(|line| (Edit::Delete, *line)));
+    ops.extend(new.iter().map(|line| (Edit::Insert, *line)));
+}
+
+fn split<'a>(old: &[&'a str], new: &[&'a str], x: usize, y: usize, ops: &mut Vec<(Edit, &'a str)>) {
+    diff_lines(&old[..x], &new[..y], ops);
+    diff_lines(&old[x..], &new[y..], ops);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff_lines, unified_diff, Edit};
+
+    #[test]
+    pub fn test_equal_texts() {
+        assert_eq!("", unified_diff("a\nb\n", "a\nb\n", "a", "b", 3));
+    }
+
+    #[test]
+    pub fn test_single_change() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+
+        assert_eq!(
+            "--- a/x.sql\n+++ b/x.sql\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n",
+            unified_diff(old, new, "a/x.sql", "b/x.sql", 3)
+        );
+    }
+
+    #[test]
+    pub fn test_separate_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\n";
+        let new = "A\nb\nc\nd\ne\nf\nG\n";
+
+        assert_eq!(
+            "--- a\n+++ b\n@@ -1,2 +1,2 @@\n-a\n+A\n b\n@@ -6,2 +6,2 @@\n f\n-g\n+G\n",
+            unified_diff(old, new, "a", "b", 1)
+        );
+    }
+
+    #[test]
+    pub fn test_insert_into_empty_and_missing_newline() {
+        assert_eq!(
+            "--- a\n+++ b\n@@ -0,0 +1,2 @@\n+x\n+y\n",
+            unified_diff("", "x\ny\n", "a", "b", 3)
+        );
+        assert_eq!(
+            "--- a\n+++ b\n@@ -1 +1 @@\n-x\n\\ No newline at end of file\n+x\n",
+            unified_diff("x", "x\n", "a", "b", 3)
+        );
+    }
+
+    #[test]
+    pub fn test_interleaved_changes() {
+        let old = "select a,\nb\nfrom t;\n";
+        let new = "SELECT\n    a,\n    b\nFROM\n    t;\n";
+        let diff = unified_diff(old, new, "a", "b", 3);
+
+        let removed: Vec<&str> = diff.lines().filter(|l| l.starts_with('-')).collect();
+        let added: Vec<&str> = diff.lines().filter(|l| l.starts_with('+')).collect();
+        assert_eq!(vec!["--- a", "-select a,", "-b", "-from t;"], removed);
+        assert_eq!(
+            vec!["+++ b", "+SELECT", "+    a,", "+    b", "+FROM", "+    t;"],
+            added
+        );
+    }
+
+    #[test]
+    pub fn test_ops_rebuild_both_texts() {
+        let mut seed: u32 = 7;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % 4
+        };
+        let lines = ["a\n", "b\n", "c\n", "d\n"];
+        for _ in 0..200 {
+            let old: Vec<&str> = (0..next() * 3).map(|_| lines[next() as usize]).collect();
+            let new: Vec<&str> = (0..next() * 3).map(|_| lines[next() as usize]).collect();
+            let mut ops = vec![];
+            diff_lines(&old, &new, &mut ops);
+
+            let rebuilt_old: Vec<&str> = ops
+                .iter()
+                .filter(|(edit, _)| *edit != Edit::Insert)
+                .map(|(_, line)| *line)
+                .collect();
+            let rebuilt_new: Vec<&str> = ops
+                .iter()
+                .filter(|(edit, _)| *edit != Edit::Delete)
+                .map(|(_, line)| *line)
+                .collect();
+            assert_eq!(old, rebuilt_old);
+            assert_eq!(new, rebuilt_new);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! Layout documents in the style of Wadler's "A prettier printer": the printer describes text,
+//! optional line breaks and indentation, and `render` lays every group out on one line when it
+//! fits within the line width, breaking all of its lines otherwise.
+
+#[derive(Debug, Clone)]
+pub(super) enum Doc {
+    Text(String),
+    /// A `--` comment or hint: nothing may follow it on its line.
+    LineComment(String),
+    /// A space when the enclosing group is flat, a newline otherwise.
+    Line,
+    /// Nothing when the enclosing group is flat, a newline otherwise.
+    SoftLine,
+    /// Always a newline, so the groups around it are never flat.
+    HardLine,
+    Concat(Vec<Doc>),
+    /// Indents the lines started inside it by one more level.
+    Nest(Box<Doc>),
+    Group(Box<Doc>),
+    /// Printed only when the enclosing group is broken.
+    IfBreak(Box<Doc>),
+}
+
+impl Doc {
+    pub(super) fn text(text: &str) -> Doc {
+        Doc::Text(text.to_string())
+    }
+
+    pub(super) fn nest(doc: Doc) -> Doc {
+        Doc::Nest(Box::new(doc))
+    }
+
+    pub(super) fn group(doc: Doc) -> Doc {
+        Doc::Group(Box::new(doc))
+    }
+
+    pub(super) fn if_break(doc: Doc) -> Doc {
+        Doc::IfBreak(Box::new(doc))
+    }
+
+    /// Width of the document laid out on one line, if it can be.
+    pub(super) fn flat_width(&self) -> Option<usize> {
+        match self {
+            Doc::Text(text) if text.contains('\n') => None,
+            Doc::Text(text) => Some(text.chars().count()),
+            Doc::LineComment(_) | Doc::HardLine => None,
+            Doc::Line => Some(1),
+            Doc::SoftLine | Doc::IfBreak(_) => Some(0),
+            Doc::Concat(docs) => docs.iter().map(Doc::flat_width).sum(),
+            Doc::Nest(doc) | Doc::Group(doc) => doc.flat_width(),
+        }
+    }
+
+    /// The first character the document prints, if it starts with text.
+    pub(super) fn first_char(&self) -> Option<char> {
+        match self {
+            Doc::Text(text) | Doc::LineComment(text) => text.chars().next(),
+            Doc::Line | Doc::HardLine => Some(' '),
+            Doc::SoftLine | Doc::IfBreak(_) => None,
+            Doc::Concat(docs) => docs.iter().find_map(Doc::first_char),
+            Doc::Nest(doc) | Doc::Group(doc) => doc.first_char(),
+        }
+    }
+
+    /// `(a, b, c)` on one line.
+    pub(super) fn parenthesized(docs: Vec<Doc>) -> Doc {
+        let mut list = vec![Doc::text("(")];
+        for (i, doc) in docs.into_iter().enumerate() {
+            if i > 0 {
+                list.push(Doc::text(", "));
+            }
+            list.push(doc);
+        }
+        list.push(Doc::text(")"));
+        Doc::Concat(list)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+// A document still to be printed, with its indentation level and layout.
+type Command<'d> = (usize, Mode, &'d Doc);
+
+struct Renderer {
+    indent_width: usize,
+    buf: String,
+    column: usize,
+    line_start: bool,
+    // Set after a line comment: whatever comes next starts a new line.
+    pending_newline: bool,
+}
+
+impl Renderer {
+    fn text(&mut self, indent: usize, text: &str) {
+        if self.pending_newline {
+            self.newline();
+            // Spaces meant to follow the comment would only indent the next line.
+            if text.trim().is_empty() {
+                return;
+            }
+        }
+        // Indentation is written lazily so that empty lines carry no trailing spaces.
+        if self.line_start {
+            let width = indent * self.indent_width;
+            self.buf.extend(std::iter::repeat_n(' ', width));
+            self.column = width;
+            self.line_start = false;
+        }
+        self.buf.push_str(text);
+        match text.rsplit_once('\n') {
+            Some((_, last_line)) => self.column = last_line.chars().count(),
+            None => self.column += text.chars().count(),
+        }
+    }
+
+    fn newline(&mut self) {
+        self.buf.push('\n');
+        self.column = 0;
+        self.line_start = true;
+        self.pending_newline = false;
+    }
+
+    /// Column the next text starts at when printed at `indent`.
+    fn next_column(&self, indent: usize) -> usize {
+        if self.line_start || self.pending_newline {
+            indent * self.indent_width
+        } else {
+            self.column
+        }
+    }
+}
+
+pub(super) fn render(doc: &Doc, max_width: usize, indent_width: usize) -> String {
+    let mut out = Renderer {
+        indent_width,
+        buf: String::new(),
+        column: 0,
+        line_start: true,
+        pending_newline: false,
+    };
+    let mut commands: Vec<Command> = vec![(0, Mode::Break, doc)];
+
+    while let Some((indent, mode, doc)) = commands.pop() {
+        match doc {
+            Doc::Text(text) => out.text(indent, text),
+            Doc::LineComment(text) => {
+                out.text(indent, text);
+                out.pending_newline = true;
+            }
+            Doc::Line | Doc::SoftLine if mode == Mode::Break => out.newline(),
+            Doc::Line if !out.pending_newline => out.text(indent, " "),
+            Doc::Line | Doc::SoftLine => {}
+            Doc::HardLine => out.newline(),
+            Doc::Concat(docs) => {
+                commands.extend(docs.iter().rev().map(|doc| (indent, mode, doc)));
+            }
+            Doc::Nest(doc) => commands.push((indent + 1, mode, doc)),
+            Doc::IfBreak(doc) if mode == Mode::Break => commands.push((indent, mode, doc)),
+            Doc::IfBreak(_) => {}
+            Doc::Group(doc) => {
+                let width = max_width as isize - out.next_column(indent) as isize;
+                let mode =
+                    if mode == Mode::Flat || fits(width, (indent, Mode::Flat, doc), &commands) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                commands.push((indent, mode, doc));
+            }
+        }
+    }
+    out.buf
+}
+
+/// Whether `next` fits in `width` columns when laid out flat, together with whatever follows
+/// it up to the next line break.
+fn fits(mut width: isize, next: Command, rest: &[Command]) -> bool {
+    let mut commands = vec![next];
+    let mut rest = rest.iter().rev();
+    while width >= 0 {
+        let (indent, mode, doc) = match commands.pop() {
+            Some(command) => command,
+            None => match rest.next() {
+                Some(command) => *command,
+                None => return true,
+            },
+        };
+        match doc {
+            // Block comments and hints may span several lines.
+            Doc::Text(text) if text.contains('\n') => {
+                let first_line = text.lines().next().unwrap_or_default();
+                return mode == Mode::Break && width >= first_line.chars().count() as isize;
+            }
+            Doc::Text(text) => width -= text.chars().count() as isize,
+            Doc::LineComment(_) | Doc::HardLine if mode == Mode::Flat => return false,
+            Doc::LineComment(text) => return width >= text.chars().count() as isize,
+            Doc::Line if mode == Mode::Flat => width -= 1,
+            Doc::SoftLine if mode == Mode::Flat => {}
+            Doc::Line | Doc::SoftLine | Doc::HardLine => return true,
+            Doc::Concat(docs) => commands.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+            Doc::Nest(doc) | Doc::Group(doc) => commands.push((indent, mode, doc)),
+            Doc::IfBreak(doc) if mode == Mode::Break => commands.push((indent, mode, doc)),
+            Doc::IfBreak(_) => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::{render, Doc};
+
+    fn list(items: &[&str]) -> Doc {
+        let mut docs = vec![Doc::text("SELECT")];
+        for (i, item) in items.iter().enumerate() {
+            docs.push(Doc::Line);
+            docs.push(Doc::text(item));
+            if i + 1 < items.len() {
+                docs.push(Doc::text(","));
+            }
+        }
+        Doc::group(Doc::Concat(vec![
+            Doc::Concat(vec![docs.remove(0), Doc::nest(Doc::Concat(docs))]),
+            Doc::text(";"),
+        ]))
+    }
+
+    #[test]
+    pub fn test_group_fits_or_breaks() {
+        let doc = list(&["a", "b"]);
+
+        assert_eq!("SELECT a, b;", render(&doc, 12, 4));
+        assert_eq!("SELECT\n    a,\n    b;", render(&doc, 11, 4));
+    }
+
+    #[test]
+    pub fn test_inner_groups_break_independently() {
+        let doc = Doc::group(Doc::Concat(vec![
+            list(&["a", "b"]),
+            Doc::Line,
+            list(&["looooooong", "c"]),
+        ]));
+
+        assert_eq!(
+            "SELECT a, b;\nSELECT\n    looooooong,\n    c;",
+            render(&doc, 20, 4)
+        );
+        assert_eq!("SELECT a, b; SELECT looooooong, c;", render(&doc, 40, 4));
+    }
+
+    #[test]
+    pub fn test_comments_and_hard_lines_break_groups() {
+        let doc = Doc::group(Doc::Concat(vec![
+            Doc::text("a"),
+            Doc::text(" "),
+            Doc::LineComment("-- c".to_string()),
+            Doc::Line,
+            Doc::text("b"),
+        ]));
+        assert_eq!("a -- c\nb", render(&doc, 80, 4));
+
+        let doc = Doc::group(Doc::Concat(vec![
+            Doc::text("a"),
+            Doc::nest(Doc::Concat(vec![
+                Doc::HardLine,
+                Doc::HardLine,
+                Doc::text("b"),
+            ])),
+        ]));
+        assert_eq!("a\n\n    b", render(&doc, 80, 4));
+
+        // Text right after a line comment moves to the next line.
+        let doc = Doc::Concat(vec![
+            Doc::LineComment("--+ hint".to_string()),
+            Doc::text("x"),
+        ]);
+        assert_eq!("--+ hint\nx", render(&doc, 80, 4));
+    }
+
+    #[test]
+    pub fn test_if_break() {
+        let doc = Doc::group(Doc::Concat(vec![
+            Doc::text("a"),
+            Doc::Line,
+            Doc::text("b"),
+            Doc::if_break(Doc::text(",")),
+        ]));
+
+        assert_eq!(Some(3), doc.flat_width());
+        assert_eq!("a b", render(&doc, 3, 4));
+        assert_eq!("a\nb,", render(&doc, 2, 4));
+    }
+}
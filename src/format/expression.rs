@@ -0,0 +1,412 @@
+use super::doc::Doc;
+use super::Printer;
+use crate::parser::expression::{
+    BinaryExpression, BinaryOperator, CaseExpression, CastExpression, DataType, DataTypePart,
+    Expression, FunctionCall, Quantifier, SimpleExpression, UnaryOperator,
+};
+use crate::parser::statement::query::Distinc;
+use crate::parser::Spellings;
+use crate::tokenizer::keyword::Keyword;
+use crate::tokenizer::Comment;
+
+impl Printer<'_> {
+    pub(super) fn expression(&mut self, expression: &Expression) -> Doc {
+        match expression {
+            Expression::SimpleExpression(simple) => self.simple_expression(simple),
+            Expression::BindVariable(text)
+            | Expression::SubstitutionVariable(text)
+            | Expression::Literal(text) => Doc::text(text),
+            Expression::TypedLiteral(keyword, literal, spellings) => Doc::Concat(vec![
+                self.keyword(*keyword, spellings.iter().next()),
+                Doc::text(" "),
+                Doc::text(literal),
+            ]),
+            Expression::Null(spellings) => self.keyword(Keyword::Null, spellings.iter().next()),
+            Expression::Unary(unary) => {
+                let spelling = unary.spellings.iter().next();
+                let operator = match unary.operator {
+                    UnaryOperator::Plus => Doc::text("+"),
+                    UnaryOperator::Minus => Doc::text("-"),
+                    UnaryOperator::Not => {
+                        Doc::Concat(vec![self.keyword(Keyword::Not, spelling), Doc::text(" ")])
+                    }
+                    UnaryOperator::Prior => {
+                        Doc::Concat(vec![self.keyword(Keyword::Prior, spelling), Doc::text(" ")])
+                    }
+                };
+                let operand = self.expression(&unary.operand);
+                // `- -1` must not become the start of a `--` comment.
+                let sign = matches!(unary.operator, UnaryOperator::Plus | UnaryOperator::Minus);
+                match sign && matches!(operand.first_char(), Some('-' | '+')) {
+                    true => Doc::Concat(vec![operator, Doc::text(" "), operand]),
+                    false => Doc::Concat(vec![operator, operand]),
+                }
+            }
+            Expression::Binary(binary) => self.binary_expression(binary),
+            Expression::IsNull(is_null) => {
+                let mut spellings = is_null.spellings.iter();
+                let mut docs = vec![self.expression(&is_null.expression), Doc::text(" ")];
+                docs.push(self.keyword(Keyword::Is, spellings.next()));
+                if is_null.not {
+                    docs.push(Doc::text(" "));
+                    docs.push(self.keyword(Keyword::Not, spellings.next()));
+                }
+                docs.push(Doc::text(" "));
+                docs.push(self.keyword(Keyword::Null, spellings.next()));
+                Doc::Concat(docs)
+            }
+            Expression::In(in_list) => {
+                let mut spellings = in_list.spellings.iter();
+                let mut docs = vec![self.expression(&in_list.expression)];
+                docs.push(self.negation(in_list.not, &mut spellings));
+                docs.push(self.keyword(Keyword::In, spellings.next()));
+                docs.push(Doc::text(" "));
+                docs.push(self.expression(&in_list.list));
+                Doc::Concat(docs)
+            }
+            Expression::Between(between) => {
+                let mut spellings = between.spellings.iter();
+                let mut docs = vec![self.expression(&between.expression)];
+                docs.push(self.negation(between.not, &mut spellings));
+                docs.push(self.keyword(Keyword::Between, spellings.next()));
+                docs.push(Doc::text(" "));
+                docs.push(self.expression(&between.low));
+                docs.push(Doc::text(" "));
+                docs.push(self.keyword(Keyword::And, spellings.next()));
+                docs.push(Doc::text(" "));
+                docs.push(self.expression(&between.high));
+                Doc::Concat(docs)
+            }
+            Expression::Like(like) => {
+                let mut spellings = like.spellings.iter();
+                let mut docs = vec![self.expression(&like.expression)];
+                docs.push(self.negation(like.not, &mut spellings));
+                docs.push(self.keyword(Keyword::Like, spellings.next()));
+                docs.push(Doc::text(" "));
+                docs.push(self.expression(&like.pattern));
+                if let Some(escape) = &like.escape {
+                    docs.push(Doc::text(" "));
+                    docs.push(self.keyword(Keyword::Escape, spellings.next()));
+                    docs.push(Doc::text(" "));
+                    docs.push(self.expression(escape));
+                }
+                Doc::Concat(docs)
+            }
+            Expression::Exists(subquery, spellings) => Doc::Concat(vec![
+                self.keyword(Keyword::Exists, spellings.iter().next()),
+                Doc::text(" "),
+                self.subquery(subquery),
+            ]),
+            Expression::Quantified(quantified) => {
+                let quantifier = match quantified.quantifier {
+                    Quantifier::Any => Keyword::Any,
+                    Quantifier::Some => Keyword::Some,
+                    Quantifier::All => Keyword::All,
+                };
+                Doc::Concat(vec![
+                    self.keyword(quantifier, quantified.spellings.iter().next()),
+                    Doc::text(" "),
+                    self.expression(&quantified.list),
+                ])
+            }
+            Expression::Subquery(subquery) => self.subquery(subquery),
+            // A condition in parentheses breaks inside them when it is too long.
+            Expression::Parenthesized(expression) if is_condition_chain(expression) => {
+                Doc::group(Doc::Concat(vec![
+                    Doc::text("("),
+                    Doc::nest(Doc::Concat(vec![
+                        Doc::SoftLine,
+                        self.expression(expression),
+                    ])),
+                    Doc::SoftLine,
+                    Doc::text(")"),
+                ]))
+            }
+            Expression::Parenthesized(expression) => Doc::Concat(vec![
+                Doc::text("("),
+                self.expression(expression),
+                Doc::text(")"),
+            ]),
+            Expression::ExpressionList(expressions) => self.expression_list(expressions),
+            Expression::FunctionCall(function) => self.function_call(function),
+            Expression::Case(case) => self.case_expression(case),
+            Expression::Cast(cast) => self.cast_expression(cast),
+            Expression::Commented(commented) => Doc::Concat(vec![
+                self.leading_comments(&commented.comments.leading),
+                self.expression(&commented.expression),
+                self.trailing_comments(&commented.comments.trailing),
+            ]),
+        }
+    }
+
+    /// `(a, b, c)`
+    pub(super) fn expression_list(&mut self, expressions: &[Expression]) -> Doc {
+        Doc::parenthesized(
+            expressions
+                .iter()
+                .map(|expression| self.expression(expression))
+                .collect(),
+        )
+    }
+
+    /// The space before a condition keyword, with NOT when the condition is negated.
+    fn negation<'s>(&mut self, not: bool, spellings: &mut impl Iterator<Item = &'s str>) -> Doc {
+        match not {
+            true => Doc::Concat(vec![
+                Doc::text(" "),
+                self.keyword(Keyword::Not, spellings.next()),
+                Doc::text(" "),
+            ]),
+            false => Doc::text(" "),
+        }
+    }
+
+    fn binary_expression(&mut self, binary: &BinaryExpression) -> Doc {
+        let keyword = match binary.operator {
+            BinaryOperator::And => Keyword::And,
+            BinaryOperator::Or => Keyword::Or,
+            operator => {
+                return Doc::Concat(vec![
+                    self.expression(&binary.left),
+                    Doc::text(" "),
+                    Doc::text(operator.as_str()),
+                    Doc::text(" "),
+                    self.expression(&binary.right),
+                ])
+            }
+        };
+
+        // A chain of ANDs or of ORs stays on one line when it fits, otherwise every operator
+        // starts a line of its own.
+        let mut operands = vec![];
+        let mut operators = vec![];
+        chain_operands(binary, binary.operator, &mut operands, &mut operators);
+        let mut docs = vec![];
+        for (i, operand) in operands.into_iter().enumerate() {
+            // Comments above an operand go before its operator, the chain breaks around them.
+            let (operand, leading, trailing) = split_comments(operand);
+            if i > 0 {
+                docs.push(Doc::Line);
+            }
+            docs.push(self.leading_comments(leading));
+            if i > 0 {
+                docs.push(self.keyword(keyword, operators[i - 1].iter().next()));
+                docs.push(Doc::text(" "));
+            }
+            // A chain of the other operator is indented below the line it starts on.
+            match is_condition_chain(operand) {
+                true => docs.push(Doc::nest(self.expression(operand))),
+                false => docs.push(self.expression(operand)),
+            }
+            docs.push(self.trailing_comments(trailing));
+        }
+        Doc::group(Doc::Concat(docs))
+    }
+
+    /// `f(a, b)`, breaking after the parenthesis when the arguments do not fit.
+    fn function_call(&mut self, function: &FunctionCall) -> Doc {
+        let mut docs = vec![];
+        for (i, name) in function.names.iter().enumerate() {
+            if i > 0 {
+                docs.push(Doc::text("."));
+            }
+            docs.push(self.identifier(name));
+        }
+        docs.push(Doc::text("("));
+        let mut arguments = vec![Doc::SoftLine];
+        let distinct = match function.distinct {
+            Distinc::Distinct => Some(Keyword::Distinct),
+            Distinc::Unique => Some(Keyword::Unique),
+            Distinc::All => None,
+        };
+        if let Some(distinct) = distinct {
+            arguments.push(self.keyword(distinct, function.spellings.iter().next()));
+            arguments.push(Doc::text(" "));
+        }
+        for (i, argument) in function.arguments.iter().enumerate() {
+            let (argument, leading, trailing) = split_comments(argument);
+            if i > 0 {
+                arguments.push(Doc::Line);
+            }
+            arguments.push(self.leading_comments(leading));
+            arguments.push(self.expression(argument));
+            if i + 1 < function.arguments.len() {
+                arguments.push(Doc::text(","));
+            }
+            arguments.push(self.trailing_comments(trailing));
+        }
+        docs.push(Doc::nest(Doc::Concat(arguments)));
+        docs.push(Doc::SoftLine);
+        docs.push(Doc::text(")"));
+        Doc::group(Doc::Concat(docs))
+    }
+
+    /// A CASE stays on one line when it fits, otherwise every branch starts a line of its own
+    /// and, with `align`, the THENs line up.
+    fn case_expression(&mut self, case: &CaseExpression) -> Doc {
+        let mut spellings = case.spellings.iter();
+        let mut docs = vec![self.keyword(Keyword::Case, spellings.next())];
+        if let Some(operand) = &case.operand {
+            docs.push(Doc::text(" "));
+            docs.push(self.expression(operand));
+        }
+
+        // Each branch is printed in source order, the padding after its condition is only
+        // added once every condition has been measured.
+        let mut whens = vec![];
+        for when in &case.when_clauses {
+            // Comments above a branch go before its WHEN.
+            let (condition, leading, trailing) = split_comments(&when.condition);
+            let mut when_spellings = when.spellings.iter();
+            let head = Doc::Concat(vec![
+                Doc::Line,
+                self.leading_comments(leading),
+                self.keyword(Keyword::When, when_spellings.next()),
+                Doc::text(" "),
+            ]);
+            let condition = self.expression(condition);
+            let width = condition.flat_width();
+            let condition = Doc::Concat(vec![head, condition, self.trailing_comments(trailing)]);
+            let result = Doc::Concat(vec![
+                Doc::text(" "),
+                self.keyword(Keyword::Then, when_spellings.next()),
+                Doc::text(" "),
+                self.expression(&when.result),
+            ]);
+            whens.push((condition, width, result));
+        }
+        let widths: Option<Vec<usize>> = whens.iter().map(|(_, width, _)| *width).collect();
+        let widths = widths.filter(|_| self.opts.align).unwrap_or_default();
+        let widest = widths.iter().copied().max().unwrap_or_default();
+
+        let mut branches = vec![];
+        for (i, (condition, _, result)) in whens.into_iter().enumerate() {
+            branches.push(condition);
+            if let Some(width) = widths.get(i).filter(|width| **width < widest) {
+                branches.push(Doc::if_break(Doc::text(&" ".repeat(widest - width))));
+            }
+            branches.push(result);
+        }
+        if let Some(else_result) = &case.else_result {
+            let (else_result, leading, trailing) = split_comments(else_result);
+            branches.push(Doc::Line);
+            branches.push(self.leading_comments(leading));
+            branches.push(self.keyword(Keyword::Else, spellings.next()));
+            branches.push(Doc::text(" "));
+            branches.push(self.expression(else_result));
+            branches.push(self.trailing_comments(trailing));
+        }
+        docs.push(Doc::nest(Doc::Concat(branches)));
+        docs.push(Doc::Line);
+        docs.push(self.keyword(Keyword::End, spellings.next()));
+        Doc::group(Doc::Concat(docs))
+    }
+
+    fn cast_expression(&mut self, cast: &CastExpression) -> Doc {
+        let mut spellings = cast.spellings.iter();
+        Doc::Concat(vec![
+            self.keyword(Keyword::Cast, spellings.next()),
+            Doc::text("("),
+            self.expression(&cast.expression),
+            Doc::text(" "),
+            self.keyword(Keyword::As, spellings.next()),
+            Doc::text(" "),
+            self.data_type(&cast.data_type),
+            Doc::text(")"),
+        ])
+    }
+
+    /// `NUMBER(10, 2)`, `TIMESTAMP(3) WITH TIME ZONE` or `hr.address_t`.
+    fn data_type(&mut self, data_type: &DataType) -> Doc {
+        let mut docs = vec![];
+        let mut spellings = data_type.spellings.iter();
+        let mut previous: Option<&DataTypePart> = None;
+        for part in &data_type.parts {
+            let spaced = !matches!(
+                (previous, part),
+                (None, _)
+                    | (Some(DataTypePart::Dot | DataTypePart::LParen), _)
+                    | (
+                        _,
+                        DataTypePart::Dot
+                            | DataTypePart::LParen
+                            | DataTypePart::RParen
+                            | DataTypePart::Comma
+                    )
+            );
+            if spaced {
+                docs.push(Doc::text(" "));
+            }
+            docs.push(match part {
+                DataTypePart::Keyword(keyword) => self.keyword(*keyword, spellings.next()),
+                DataTypePart::Name(name) => self.identifier(name),
+                DataTypePart::Value(value) => Doc::text(value),
+                DataTypePart::Dot => Doc::text("."),
+                DataTypePart::Comma => Doc::text(","),
+                DataTypePart::LParen => Doc::text("("),
+                DataTypePart::RParen => Doc::text(")"),
+            });
+            previous = Some(part);
+        }
+        Doc::Concat(docs)
+    }
+
+    fn simple_expression(&mut self, simple: &SimpleExpression) -> Doc {
+        let mut docs = vec![];
+        for qualifier in [&simple.schema, &simple.table].into_iter().flatten() {
+            docs.push(self.identifier(qualifier));
+            docs.push(Doc::text("."));
+        }
+        docs.push(self.identifier(&simple.column));
+        if simple.outer_join {
+            docs.push(Doc::text("(+)"));
+        }
+        Doc::Concat(docs)
+    }
+}
+
+fn is_condition_chain(expression: &Expression) -> bool {
+    match expression {
+        Expression::Commented(commented) => is_condition_chain(&commented.expression),
+        expression => matches!(
+            expression,
+            Expression::Binary(BinaryExpression {
+                operator: BinaryOperator::And | BinaryOperator::Or,
+                ..
+            })
+        ),
+    }
+}
+
+/// The expression without its comments, and the comments before and after it.
+fn split_comments(expression: &Expression) -> (&Expression, &[Comment], &[Comment]) {
+    match expression {
+        Expression::Commented(commented) => (
+            &commented.expression,
+            &commented.comments.leading,
+            &commented.comments.trailing,
+        ),
+        expression => (expression, &[], &[]),
+    }
+}
+
+/// The operands of `binary` and of the operations with the same `operator` nested in it, and
+/// the spellings of the operators between them.
+fn chain_operands<'e>(
+    binary: &'e BinaryExpression,
+    operator: BinaryOperator,
+    operands: &mut Vec<&'e Expression>,
+    operators: &mut Vec<&'e Spellings>,
+) {
+    for (i, operand) in [&binary.left, &binary.right].into_iter().enumerate() {
+        if i > 0 {
+            operators.push(&binary.spellings);
+        }
+        match operand.as_ref() {
+            Expression::Binary(nested) if nested.operator == operator => {
+                chain_operands(nested, operator, operands, operators)
+            }
+            operand => operands.push(operand),
+        }
+    }
+}
@@ -0,0 +1,811 @@
+mod doc;
+mod expression;
+mod query;
+
+use self::doc::Doc;
+use crate::parser::statement::Statement;
+use crate::parser::{Identifier, Parser, ParserError};
+use crate::tokenizer::keyword::Keyword;
+use crate::tokenizer::{Comment, CommentKind};
+use std::str::FromStr;
+
+/// Letter case applied to keywords or to unquoted names.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Case {
+    Upper,
+    Lower,
+    /// Keep the spelling from the source.
+    Preserve,
+}
+
+impl Case {
+    fn apply(self, text: &str) -> String {
+        match self {
+            Case::Upper => text.to_uppercase(),
+            Case::Lower => text.to_lowercase(),
+            Case::Preserve => text.to_string(),
+        }
+    }
+}
+
+impl FromStr for Case {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Case, String> {
+        match s {
+            "upper" => Ok(Case::Upper),
+            "lower" => Ok(Case::Lower),
+            "preserve" => Ok(Case::Preserve),
+            _ => Err(format!("expected upper, lower or preserve, found '{}'", s)),
+        }
+    }
+}
+
+/// Where the separating comma goes when a list is broken over several lines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommaStyle {
+    /// `a,` at the end of the line.
+    Trailing,
+    /// `, b` at the start of the next one.
+    Leading,
+}
+
+impl FromStr for CommaStyle {
+    type Err = String;
+    fn from_str(s: &str) -> Result<CommaStyle, String> {
+        match s {
+            "trailing" => Ok(CommaStyle::Trailing),
+            "leading" => Ok(CommaStyle::Leading),
+            _ => Err(format!("expected trailing or leading, found '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Number of spaces per indentation level.
+    pub indent_width: usize,
+    /// Line width the layout tries to stay within.
+    pub max_width: usize,
+    pub keyword_case: Case,
+    /// Applies to unquoted names only, quoted ones are case sensitive.
+    pub identifier_case: Case,
+    pub comma_style: CommaStyle,
+    /// Line up the parts of consecutive lines that belong together, such as the branches of
+    /// a CASE expression.
+    pub align: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent_width: 4,
+            max_width: 100,
+            keyword_case: Case::Upper,
+            identifier_case: Case::Preserve,
+            comma_style: CommaStyle::Trailing,
+            align: true,
+        }
+    }
+}
+
+/// Renders parsed statements back to SQL text, each one terminated by `;`.
+pub fn format(statements: &[Statement], opts: &FormatOptions) -> String {
+    Printer::new(opts).statements(statements)
+}
+
+/// Parses and formats `source`.
+pub fn format_source(source: &str, opts: &FormatOptions) -> Result<String, ParserError> {
+    let statements = Parser::new().parse(source)?;
+    Ok(format(&statements, opts))
+}
+
+struct Printer<'a> {
+    opts: &'a FormatOptions,
+    // Spelling of the last keyword printed from the source, for `Case::Preserve`.
+    last_spelling: Option<String>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(opts: &'a FormatOptions) -> Printer<'a> {
+        Printer {
+            opts,
+            last_spelling: None,
+        }
+    }
+
+    fn statements(mut self, statements: &[Statement]) -> String {
+        let mut docs = vec![];
+        for (i, statement) in statements.iter().enumerate() {
+            if i > 0 {
+                docs.push(Doc::HardLine);
+            }
+            docs.push(self.statement(statement));
+            docs.push(Doc::HardLine);
+        }
+        doc::render(
+            &Doc::Concat(docs),
+            self.opts.max_width,
+            self.opts.indent_width,
+        )
+    }
+
+    /// `keyword` with its `spelling` from the source, if it has one. Under `Case::Preserve` a
+    /// keyword the source leaves out, like an implicit AS, takes the letter case of the keyword
+    /// printed before it.
+    fn keyword(&mut self, keyword: Keyword, spelling: Option<&str>) -> Doc {
+        let text = match (self.opts.keyword_case, spelling) {
+            (Case::Preserve, Some(spelling)) => {
+                self.last_spelling = Some(spelling.to_string());
+                spelling.to_string()
+            }
+            (Case::Preserve, None) => match &self.last_spelling {
+                Some(last) => same_case(keyword.as_str(), last),
+                None => keyword.as_str().to_string(),
+            },
+            (case, _) => case.apply(keyword.as_str()),
+        };
+        Doc::Text(text)
+    }
+
+    fn identifier(&self, identifier: &Identifier) -> Doc {
+        match identifier {
+            Identifier::Bare(name) => Doc::Text(self.opts.identifier_case.apply(name)),
+            Identifier::Quoted(name) => Doc::text(name),
+        }
+    }
+
+    /// An optimizer hint verbatim, it has to stay on the line of its keyword.
+    fn hint(&self, hint: &str) -> Doc {
+        match hint.starts_with("--") {
+            true => Doc::LineComment(hint.to_string()),
+            false => Doc::text(hint),
+        }
+    }
+
+    fn comment(&self, comment: &Comment) -> Doc {
+        match comment.kind {
+            CommentKind::Line => Doc::LineComment(comment.text.to_string()),
+            CommentKind::Block => Doc::text(&comment.text),
+        }
+    }
+
+    /// Each comment on its own line at the current indentation.
+    fn leading_comments(&self, comments: &[Comment]) -> Doc {
+        Doc::Concat(
+            comments
+                .iter()
+                .flat_map(|comment| [self.comment(comment), Doc::HardLine])
+                .collect(),
+        )
+    }
+
+    /// Comments at the end of the current line.
+    fn trailing_comments(&self, comments: &[Comment]) -> Doc {
+        Doc::Concat(
+            comments
+                .iter()
+                .flat_map(|comment| [Doc::text(" "), self.comment(comment)])
+                .collect(),
+        )
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Doc {
+        match statement {
+            Statement::Query(subquery) => Doc::Concat(vec![
+                self.leading_comments(&subquery.comments.leading),
+                self.subquery_body(subquery),
+                Doc::text(";"),
+                self.trailing_comments(&subquery.comments.trailing),
+            ]),
+            Statement::Comments(comments) => {
+                let mut docs = vec![];
+                for (i, comment) in comments.iter().enumerate() {
+                    if i > 0 {
+                        docs.push(Doc::HardLine);
+                    }
+                    docs.push(self.comment(comment));
+                }
+                Doc::Concat(docs)
+            }
+        }
+    }
+}
+
+/// `text` in the letter case of `like`: upper, lower, or capitalized when `like` mixes them.
+fn same_case(text: &str, like: &str) -> String {
+    if like == like.to_uppercase() {
+        return text.to_uppercase();
+    }
+    if like == like.to_lowercase() {
+        return text.to_lowercase();
+    }
+    let lower = text.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => lower,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format, format_source, Case, CommaStyle, FormatOptions};
+    use crate::parser::Parser;
+
+    fn format_query(query: &str, opts: &FormatOptions) -> String {
+        let statements = Parser::new().parse(query).unwrap();
+        format(&statements, opts)
+    }
+
+    #[test]
+    pub fn test_format_select_list() {
+        let query = "select unique t.*, s.t.c1 a1, c2 as a2, c3 from tab;\n";
+
+        assert_eq!(
+            "SELECT UNIQUE t.*, s.t.c1 AS a1, c2 AS a2, c3 FROM tab;\n",
+            format_query(query, &FormatOptions::default())
+        );
+
+        let opts = FormatOptions {
+            max_width: 30,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            "SELECT UNIQUE\n    t.*,\n    s.t.c1 AS a1,\n    c2 AS a2,\n    c3\nFROM tab;\n",
+            format_query(query, &opts)
+        );
+    }
+
+    #[test]
+    pub fn test_format_from_alias_and_indent() {
+        let query = "select distinct a from sch.tab t; select * from b";
+        let opts = FormatOptions {
+            indent_width: 2,
+            max_width: 16,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            "SELECT DISTINCT\n  a\nFROM sch.tab t;\n\nSELECT * FROM b;\n",
+            format_query(query, &opts)
+        );
+    }
+
+    #[test]
+    pub fn test_format_quoted_identifiers() {
+        let query = "select \"Mixed Case\" \"a\"\"b\", s.\"t\".c from \"Sch\".T \"x\"";
+
+        assert_eq!(
+            "SELECT \"Mixed Case\" AS \"a\"\"b\", s.\"t\".c FROM \"Sch\".T \"x\";\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_variables() {
+        let query = "select :id, :new.col c, &&var from t";
+
+        assert_eq!(
+            "SELECT :id, :new.col AS c, &&var FROM t;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_keywords_as_names() {
+        let query = "select Type, name x, t.rowid, ROWNUM from t";
+
+        assert_eq!(
+            "SELECT Type, name AS x, t.rowid, ROWNUM FROM t;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_case() {
+        let query = "Select Distinct Col, \"Mixed\" As Alias, t.x y From Sch.Tab t";
+        let opts = FormatOptions {
+            keyword_case: Case::Lower,
+            identifier_case: Case::Upper,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            "select distinct COL, \"Mixed\" as ALIAS, T.X as Y from SCH.TAB T;\n",
+            format_source(query, &opts).unwrap()
+        );
+
+        let opts = FormatOptions {
+            keyword_case: Case::Preserve,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            "Select Distinct Col, \"Mixed\" As Alias, t.x As y From Sch.Tab t;\n",
+            format_source(query, &opts).unwrap()
+        );
+        assert_eq!(
+            "Select Distinct Col, \"Mixed\" As Alias, t.x As y From Sch.Tab t;\n",
+            format(&Parser::new().parse(query).unwrap(), &opts)
+        );
+
+        // An added AS follows the keyword before it and leaves the written ones their spelling.
+        assert_eq!(
+            "select a as b, c As d, e AS f from t;\n",
+            format_source("select a b, c As d, e AS f from t", &opts).unwrap()
+        );
+        assert_eq!(
+            "SELECT a AS b, c as d FROM t;\n",
+            format_source("SELECT a b, c as d FROM t", &opts).unwrap()
+        );
+
+        // Keywords used as names or left out do not take the spelling of a later keyword.
+        assert_eq!(
+            "select last, a from t order by a nulls LAST;\n",
+            format_source("select last, a from t order by a nulls LAST", &opts).unwrap()
+        );
+        assert_eq!(
+            "select Count(x) from t where a > ALL (select b from u);\n",
+            format_source(
+                "select all Count(all x) from t where a > ALL (select b from u)",
+                &opts
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_format_from_list() {
+        let query = "select d.name, e.ename from hr.dept@remote.example.com d, table(d.emps) e, \
+            (select x from y) v";
+
+        assert_eq!(
+            "SELECT d.name, e.ename FROM hr.dept@remote.example.com d, TABLE(d.emps) e, \
+            (SELECT x FROM y) v;\n",
+            format_query(query, &FormatOptions::default())
+        );
+
+        let opts = FormatOptions {
+            max_width: 40,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            "SELECT d.name, e.ename\n\
+            FROM\n    \
+                hr.dept@remote.example.com d,\n    \
+                TABLE(d.emps) e,\n    \
+                (SELECT x FROM y) v;\n",
+            format_query(query, &opts)
+        );
+        assert_eq!(
+            "SELECT a\nFROM\n    a, -- first\n    b;\n",
+            format_query("select a from a, -- first\n b", &opts)
+        );
+    }
+
+    #[test]
+    pub fn test_format_joins() {
+        let query = "select a.x, b.y from a join b on a.id = b.id and a.k<>b.k \
+            left outer join c using (id, k) cross join d natural join e \
+            cross apply (select g.x from g) h outer apply table(a.t) u";
+
+        assert_eq!(
+            "SELECT a.x, b.y\n\
+            FROM\n    \
+                a\n    \
+                JOIN b\n        \
+                    ON a.id = b.id AND a.k <> b.k\n    \
+                LEFT OUTER JOIN c\n        \
+                    USING (id, k)\n    \
+                CROSS JOIN d\n    \
+                NATURAL JOIN e\n    \
+                CROSS APPLY (SELECT g.x FROM g) h\n    \
+                OUTER APPLY TABLE(a.t) u;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_partitioned_outer_join() {
+        let query = "select * from sales s partition by s.product \
+            right join times t on s.time_id = t.time_id(+), -- dense\n\
+            dual";
+
+        assert_eq!(
+            "SELECT *\n\
+            FROM\n    \
+                sales s PARTITION BY (s.product)\n    \
+                RIGHT JOIN times t\n        \
+                    ON s.time_id = t.time_id(+), -- dense\n    \
+                dual;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_expressions() {
+        let query = "select -a*(b+c)||'x' s, date '2024-01-31', null n from t join u \
+            on not t.a=u.a and (t.b is not null or t.c not in (1,2)) \
+            and t.d not between 1 and 10 and t.e like 'a!_%' escape '!'";
+
+        assert_eq!(
+            "SELECT -a * (b + c) || 'x' AS s, DATE '2024-01-31', NULL AS n\n\
+            FROM\n    \
+                t\n    \
+                JOIN u\n        \
+                    ON NOT t.a = u.a\n        \
+                    AND (t.b IS NOT NULL OR t.c NOT IN (1, 2))\n        \
+                    AND t.d NOT BETWEEN 1 AND 10\n        \
+                    AND t.e LIKE 'a!_%' ESCAPE '!';\n",
+            format_query(query, &FormatOptions::default())
+        );
+        assert_eq!(
+            "SELECT a\n\
+            FROM\n    \
+                t\n    \
+                JOIN u\n        \
+                    ON t.a > ALL (SELECT b FROM v) AND EXISTS (SELECT 1 FROM w);\n",
+            format_query(
+                "select a from t join u on t.a > all (select b from v) and exists (select 1 from w)",
+                &FormatOptions::default()
+            )
+        );
+    }
+
+    #[test]
+    pub fn test_format_functions_case_cast() {
+        let query = "select NVL(a,0) n, COUNT(*), COUNT(distinct b), \
+            cast(c as number(10,2)), DECODE(d, 1, 'one', 'other') from t";
+
+        assert_eq!(
+            "SELECT\n    \
+                NVL(a, 0) AS n,\n    \
+                COUNT(*),\n    \
+                COUNT(DISTINCT b),\n    \
+                CAST(c AS NUMBER(10, 2)),\n    \
+                DECODE(d, 1, 'one', 'other')\n\
+            FROM t;\n",
+            format_query(
+                query,
+                &FormatOptions {
+                    max_width: 60,
+                    ..FormatOptions::default()
+                }
+            )
+        );
+
+        let query = "select case when a = 1 then 'one' when long_name is null then 'none' \
+            else 'other' end x, case b when 1 then 2 end from t";
+        assert_eq!(
+            "SELECT\n    \
+                CASE\n        \
+                    WHEN a = 1             THEN 'one'\n        \
+                    WHEN long_name IS NULL THEN 'none'\n        \
+                    ELSE 'other'\n    \
+                END AS x,\n    \
+                CASE b WHEN 1 THEN 2 END\n\
+            FROM t;\n",
+            format_query(
+                query,
+                &FormatOptions {
+                    max_width: 50,
+                    ..FormatOptions::default()
+                }
+            )
+        );
+        assert_eq!(
+            "SELECT\n    \
+                CASE\n        \
+                    WHEN a = 1 THEN 'one'\n        \
+                    WHEN long_name IS NULL THEN 'none'\n        \
+                    ELSE 'other'\n    \
+                END AS x,\n    \
+                CASE b WHEN 1 THEN 2 END\n\
+            FROM t;\n",
+            format_query(
+                query,
+                &FormatOptions {
+                    max_width: 50,
+                    align: false,
+                    ..FormatOptions::default()
+                }
+            )
+        );
+
+        // Every branch keeps the spellings it was written with, aligned or not.
+        let query =
+            "select case when a is NULL then null when long_name is Null then 1 end x from t";
+        let opts = FormatOptions {
+            keyword_case: Case::Preserve,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            "select case when a is NULL then null when long_name is Null then 1 end as x from t;\n",
+            format_source(query, &opts).unwrap()
+        );
+        assert_eq!(
+            "select\n    \
+                case\n        \
+                    when a is NULL         then null\n        \
+                    when long_name is Null then 1\n    \
+                end as x\n\
+            from t;\n",
+            format_source(
+                query,
+                &FormatOptions {
+                    max_width: 40,
+                    ..opts
+                }
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_format_signs_round_trip() {
+        for (query, expected) in [
+            ("select - -1 from dual", "SELECT - -1 FROM dual;\n"),
+            (
+                "select a from t where x = - -b",
+                "SELECT a FROM t WHERE x = - -b;\n",
+            ),
+            (
+                "select -(-a), + -a, -+a, a - -b from t",
+                "SELECT -(-a), + -a, - +a, a - -b FROM t;\n",
+            ),
+        ] {
+            let formatted = format_query(query, &FormatOptions::default());
+            assert_eq!(expected, formatted);
+
+            // Formatting again must read back the same statement, keyword spellings included.
+            let opts = FormatOptions {
+                keyword_case: Case::Preserve,
+                ..FormatOptions::default()
+            };
+            let original = Parser::new().parse(query).unwrap();
+            let reparsed = Parser::new().parse(&format_query(query, &opts)).unwrap();
+            assert_eq!(format!("{:?}", original), format!("{:?}", reparsed));
+        }
+    }
+
+    #[test]
+    pub fn test_format_where_group_order() {
+        let query = "select d, count_col from t where a = 1 and b = 2 \
+            group by rollup(d, e), cube((a, b), c), grouping sets ((a, b), ()) \
+            having c > 1 order by d desc nulls last, e";
+
+        assert_eq!(
+            "SELECT d, count_col\n\
+            FROM t\n\
+            WHERE a = 1 AND b = 2\n\
+            GROUP BY ROLLUP(d, e), CUBE((a, b), c), GROUPING SETS ((a, b), ())\n\
+            HAVING c > 1\n\
+            ORDER BY d DESC NULLS LAST, e;\n",
+            format_query(query, &FormatOptions::default())
+        );
+        assert_eq!(
+            "SELECT a FROM t ORDER SIBLINGS BY a ASC;\n",
+            format_query(
+                "select a from t order siblings by a asc",
+                &FormatOptions::default()
+            )
+        );
+    }
+
+    #[test]
+    pub fn test_format_breaks_long_conditions() {
+        let query = "select a from t where t.salary > 1000 and (t.job = 'CLERK' \
+            or t.job = 'MANAGER' or t.bonus is not null and t.hired > date '2020-01-01') \
+            and exists (select 1 from u where u.id = t.id) group by a, -- first\n b";
+        let opts = FormatOptions {
+            max_width: 50,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            "SELECT a\n\
+            FROM t\n\
+            WHERE\n    \
+                t.salary > 1000\n    \
+                AND (\n        \
+                    t.job = 'CLERK'\n        \
+                    OR t.job = 'MANAGER'\n        \
+                    OR t.bonus IS NOT NULL\n            \
+                        AND t.hired > DATE '2020-01-01'\n    \
+                )\n    \
+                AND EXISTS (SELECT 1 FROM u WHERE u.id = t.id)\n\
+            GROUP BY\n    \
+                a, -- first\n    \
+                b;\n",
+            format_query(query, &opts)
+        );
+    }
+
+    #[test]
+    pub fn test_format_comma_style() {
+        let query = "select a, b -- second\n, c from t";
+        let opts = FormatOptions {
+            comma_style: CommaStyle::Leading,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            "SELECT\n    a\n    , b -- second\n    , c\nFROM t;\n",
+            format_query(query, &opts)
+        );
+        assert_eq!(
+            "SELECT a, c FROM t;\n",
+            format_query("select a, c from t", &opts)
+        );
+        assert_eq!(
+            "SELECT\n    a,\n    b, -- second\n    c\nFROM t;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_comments() {
+        let query = "-- header\n\
+            select /* cols */ a, -- first\n\
+            -- second\n\
+            b -- last\n\
+            from -- tables\n\
+            t; -- done\n\
+            -- trailer";
+
+        assert_eq!(
+            "-- header\n\
+            SELECT /* cols */\n    \
+                a, -- first\n    \
+                -- second\n    \
+                b -- last\n\
+            FROM\n    \
+                -- tables\n    \
+                t; -- done\n\
+            \n\
+            -- trailer\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_keeps_misplaced_comments() {
+        let query = "select s. /* odd */ t.c from t\n-- before semicolon\n;";
+
+        assert_eq!(
+            "SELECT s.t.c FROM t; /* odd */ -- before semicolon\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_condition_comments() {
+        let query = "select a from t where -- w\n x = 1 -- c4\n  -- about y\n  and y = 2 -- c5\n\
+            group by a having count(*) > 1 -- h1\n or max(b) < 2\n\
+            order by a";
+
+        assert_eq!(
+            "SELECT a\n\
+            FROM t\n\
+            WHERE\n    \
+                -- w\n    \
+                x = 1 -- c4\n    \
+                -- about y\n    \
+                AND y = 2 -- c5\n\
+            GROUP BY a\n\
+            HAVING\n    \
+                count(*) > 1 -- h1\n    \
+                OR max(b) < 2\n\
+            ORDER BY a;\n",
+            format_query(query, &FormatOptions::default())
+        );
+
+        let query = "select a from t join u on t.a = u.a -- j1\n and (t.b = 1 /* b */ or t.c = 2)";
+        assert_eq!(
+            "SELECT a\n\
+            FROM\n    \
+                t\n    \
+                JOIN u\n        \
+                    ON t.a = u.a -- j1\n        \
+                    AND (t.b = 1 /* b */ OR t.c = 2);\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_case_and_argument_comments() {
+        let query = "select case -- why\n when a=1 then 2 -- one\n else 3 end x, \
+            nvl(a, -- dflt\n 0) from t";
+
+        assert_eq!(
+            "SELECT\n    \
+                CASE\n        \
+                    -- why\n        \
+                    WHEN a = 1 THEN 2 -- one\n        \
+                    ELSE 3\n    \
+                END AS x,\n    \
+                nvl(\n        \
+                    a, -- dflt\n        \
+                    0\n    \
+                )\n\
+            FROM t;\n",
+            format_query(query, &FormatOptions::default())
+        );
+
+        let query =
+            "select case a\n -- first\n when 1 then 'x'\n -- last\n else 'y' -- end\n end from t";
+        assert_eq!(
+            "SELECT\n    \
+                CASE a\n        \
+                    -- first\n        \
+                    WHEN 1 THEN 'x'\n        \
+                    -- last\n        \
+                    ELSE 'y' -- end\n    \
+                END\n\
+            FROM t;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_comments_around_distinct() {
+        for (query, expected) in [
+            (
+                "select /*+ h */ /* C3 */ distinct a from t;",
+                "SELECT /*+ h */ /* C3 */ DISTINCT a FROM t;\n",
+            ),
+            (
+                "select -- C0\n /*+ h */ distinct a from t;",
+                "SELECT -- C0\n/*+ h */ DISTINCT\n    a\nFROM t;\n",
+            ),
+            (
+                "select /* C2 */ unique /* C4 */ a from t;",
+                "SELECT /* C2 */ UNIQUE /* C4 */ a FROM t;\n",
+            ),
+            (
+                "select all /* C5 */ a from t;",
+                "SELECT /* C5 */ a FROM t;\n",
+            ),
+        ] {
+            let formatted = format_query(query, &FormatOptions::default());
+            assert_eq!(expected, formatted);
+            // A second pass leaves every comment where the first one put it.
+            assert_eq!(
+                formatted,
+                format_query(&formatted, &FormatOptions::default())
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_format_hints() {
+        let query = "select /*+ INDEX(t t_idx)\n PARALLEL */ distinct a from t;\n\
+            select --+ FULL(t)\n b from t";
+
+        assert_eq!(
+            "SELECT /*+ INDEX(t t_idx)\n PARALLEL */ DISTINCT\n    a\nFROM t;\n\
+            \n\
+            SELECT --+ FULL(t)\n    \
+                b\nFROM t;\n",
+            format_query(query, &FormatOptions::default())
+        );
+    }
+
+    #[test]
+    pub fn test_format_subquery() {
+        let query = "(select a from b)";
+
+        assert_eq!(
+            "(SELECT a FROM b);\n",
+            format_query(query, &FormatOptions::default())
+        );
+
+        let opts = FormatOptions {
+            max_width: 14,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            "(\n    SELECT a\n    FROM b\n);\n",
+            format_query(query, &opts)
+        );
+    }
+}
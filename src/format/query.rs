@@ -0,0 +1,400 @@
+use super::doc::Doc;
+use super::{CommaStyle, Printer};
+use crate::parser::expression::Expression;
+use crate::parser::statement::query::{
+    Apply, ApplyType, Distinc, From, GroupByItem, GroupingElement, InnerJoin, InnerJoinType, Join,
+    JoinClause, JoinCondition, NullsPosition, OrderByItem, OuterJoin, OuterJoinType, QueryBlock,
+    QueryPartition, QueryTableExpression, SelectItem, SortDirection, SubQuery, TableReference,
+};
+use crate::parser::Comments;
+use crate::tokenizer::keyword::Keyword;
+
+impl Printer<'_> {
+    pub(super) fn subquery(&mut self, subquery: &SubQuery) -> Doc {
+        Doc::Concat(vec![
+            self.leading_comments(&subquery.comments.leading),
+            self.subquery_body(subquery),
+            self.trailing_comments(&subquery.comments.trailing),
+        ])
+    }
+
+    pub(super) fn subquery_body(&mut self, subquery: &SubQuery) -> Doc {
+        let mut docs = vec![];
+        if let Some(sub_query) = &subquery.sub_query {
+            docs.push(Doc::group(Doc::Concat(vec![
+                Doc::text("("),
+                Doc::nest(Doc::Concat(vec![Doc::SoftLine, self.subquery(sub_query)])),
+                Doc::SoftLine,
+                Doc::text(")"),
+            ])));
+        }
+        if let Some(query_block) = &subquery.query_block {
+            docs.push(self.query_block(query_block));
+        }
+        Doc::Concat(docs)
+    }
+
+    /// Lays the whole query block out on one line when it fits, otherwise every clause starts
+    /// a line and breaks its own list only if that is still too long.
+    fn query_block(&mut self, query_block: &QueryBlock) -> Doc {
+        let leading = self.leading_comments(&query_block.comments.leading);
+
+        let mut spellings = query_block.spellings.iter();
+        let mut select = vec![self.keyword(Keyword::Select, spellings.next())];
+        if let Some(hint) = &query_block.hint {
+            select.push(Doc::text(" "));
+            select.push(self.hint(hint));
+        }
+        // Each comment stays after the keyword it follows in the source.
+        select.push(self.trailing_comments(&query_block.comments.trailing));
+        select.push(self.trailing_comments(&query_block.distinct_comments.leading));
+        let distinct = match query_block.distinct {
+            Distinc::Distinct => Some(Keyword::Distinct),
+            Distinc::Unique => Some(Keyword::Unique),
+            Distinc::All => None,
+        };
+        if let Some(distinct) = distinct {
+            select.push(Doc::text(" "));
+            select.push(self.keyword(distinct, spellings.next()));
+        }
+        select.push(self.trailing_comments(&query_block.distinct_comments.trailing));
+        select.push(
+            self.list(&query_block.select_list, Self::select_item, |item| {
+                &item.comments
+            }),
+        );
+
+        let mut clauses = vec![Doc::group(Doc::Concat(select))];
+        if !query_block.from.is_empty() {
+            clauses.push(Doc::Line);
+            clauses.push(Doc::group(Doc::Concat(vec![
+                self.keyword(Keyword::From, spellings.next()),
+                self.list(&query_block.from, Self::from, From::comments),
+            ])));
+        }
+        if let Some(condition) = &query_block.where_clause {
+            clauses.push(Doc::Line);
+            let keyword = self.keyword(Keyword::Where, spellings.next());
+            clauses.push(self.condition_clause(keyword, condition));
+        }
+        if !query_block.group_by.is_empty() {
+            clauses.push(Doc::Line);
+            clauses.push(Doc::group(Doc::Concat(vec![
+                self.keyword(Keyword::Group, spellings.next()),
+                Doc::text(" "),
+                self.keyword(Keyword::By, spellings.next()),
+                self.list(&query_block.group_by, Self::group_by_item, |item| {
+                    &item.comments
+                }),
+            ])));
+        }
+        if let Some(condition) = &query_block.having {
+            clauses.push(Doc::Line);
+            let keyword = self.keyword(Keyword::Having, spellings.next());
+            clauses.push(self.condition_clause(keyword, condition));
+        }
+        if let Some(order_by) = &query_block.order_by {
+            clauses.push(Doc::Line);
+            let mut spellings = order_by.spellings.iter();
+            let mut docs = vec![
+                self.keyword(Keyword::Order, spellings.next()),
+                Doc::text(" "),
+            ];
+            if order_by.siblings {
+                docs.push(self.keyword(Keyword::Siblings, spellings.next()));
+                docs.push(Doc::text(" "));
+            }
+            docs.push(self.keyword(Keyword::By, spellings.next()));
+            docs.push(self.list(&order_by.items, Self::order_by_item, |item| &item.comments));
+            clauses.push(Doc::group(Doc::Concat(docs)));
+        }
+        Doc::Concat(vec![leading, Doc::group(Doc::Concat(clauses))])
+    }
+
+    /// `items` separated by commas, one per line and indented under the clause keyword when
+    /// they do not fit on its line. `comma_style` decides which end of the line gets the comma.
+    fn list<T>(
+        &mut self,
+        items: &[T],
+        mut print: impl FnMut(&mut Self, &T) -> Doc,
+        comments: impl Fn(&T) -> &Comments,
+    ) -> Doc {
+        let mut docs = vec![];
+        for (i, item) in items.iter().enumerate() {
+            let leading_comma = self.opts.comma_style == CommaStyle::Leading && i > 0;
+            // A leading comma starts the line, after the comments standing above the item.
+            docs.push(if leading_comma {
+                Doc::SoftLine
+            } else {
+                Doc::Line
+            });
+            docs.push(self.leading_comments(&comments(item).leading));
+            if leading_comma {
+                docs.push(Doc::text(", "));
+            }
+            docs.push(print(self, item));
+            if self.opts.comma_style == CommaStyle::Trailing && i + 1 < items.len() {
+                docs.push(Doc::text(","));
+            }
+            docs.push(self.trailing_comments(&comments(item).trailing));
+        }
+        Doc::nest(Doc::Concat(docs))
+    }
+
+    /// WHERE or HAVING, the condition indented on the next line when it does not fit.
+    fn condition_clause(&mut self, keyword: Doc, condition: &Expression) -> Doc {
+        Doc::group(Doc::Concat(vec![
+            keyword,
+            Doc::nest(Doc::Concat(vec![Doc::Line, self.expression(condition)])),
+        ]))
+    }
+
+    fn group_by_item(&mut self, item: &GroupByItem) -> Doc {
+        self.grouping_element(&item.element)
+    }
+
+    fn grouping_element(&mut self, element: &GroupingElement) -> Doc {
+        match element {
+            GroupingElement::Expression(expression) => self.expression(expression),
+            GroupingElement::Empty => Doc::text("()"),
+            GroupingElement::Rollup(expressions, spellings) => Doc::Concat(vec![
+                self.keyword(Keyword::Rollup, spellings.iter().next()),
+                self.expression_list(expressions),
+            ]),
+            GroupingElement::Cube(expressions, spellings) => Doc::Concat(vec![
+                self.keyword(Keyword::Cube, spellings.iter().next()),
+                self.expression_list(expressions),
+            ]),
+            GroupingElement::GroupingSets(elements, spellings) => {
+                let mut spellings = spellings.iter();
+                Doc::Concat(vec![
+                    self.keyword(Keyword::Grouping, spellings.next()),
+                    Doc::text(" "),
+                    self.keyword(Keyword::Sets, spellings.next()),
+                    Doc::text(" "),
+                    Doc::parenthesized(
+                        elements
+                            .iter()
+                            .map(|element| self.grouping_element(element))
+                            .collect(),
+                    ),
+                ])
+            }
+        }
+    }
+
+    fn order_by_item(&mut self, item: &OrderByItem) -> Doc {
+        let mut docs = vec![self.expression(&item.expression)];
+        let mut spellings = item.spellings.iter();
+        if let Some(direction) = &item.direction {
+            let direction = match direction {
+                SortDirection::Asc => Keyword::Asc,
+                SortDirection::Desc => Keyword::Desc,
+            };
+            docs.push(Doc::text(" "));
+            docs.push(self.keyword(direction, spellings.next()));
+        }
+        if let Some(nulls) = &item.nulls {
+            let nulls = match nulls {
+                NullsPosition::First => Keyword::First,
+                NullsPosition::Last => Keyword::Last,
+            };
+            docs.push(Doc::text(" "));
+            docs.push(self.keyword(Keyword::Nulls, spellings.next()));
+            docs.push(Doc::text(" "));
+            docs.push(self.keyword(nulls, spellings.next()));
+        }
+        Doc::Concat(docs)
+    }
+
+    fn select_item(&mut self, item: &SelectItem) -> Doc {
+        let mut docs = vec![self.expression(&item.expression)];
+        if let Some(alias) = &item.alias {
+            docs.push(Doc::text(" "));
+            // An alias written without AS gets one.
+            docs.push(self.keyword(Keyword::As, item.spellings.iter().next()));
+            docs.push(Doc::text(" "));
+            docs.push(self.identifier(alias));
+        }
+        Doc::Concat(docs)
+    }
+
+    fn from(&mut self, from: &From) -> Doc {
+        match from {
+            From::TableReference(table_reference) => self.table_reference(table_reference),
+            From::Join(join) => self.join(join),
+        }
+    }
+
+    /// Every join starts a line below the first table, its condition indented beneath it.
+    fn join(&mut self, join: &Join) -> Doc {
+        let mut docs = vec![
+            self.table_reference(&join.table_reference),
+            self.trailing_comments(&join.table_reference.comments().trailing),
+        ];
+        for clause in &join.joins {
+            // The partition of the left side ends the line before the join.
+            if let JoinClause::OuterJoin(OuterJoin {
+                partition_by: Some(partition_by),
+                ..
+            }) = clause
+            {
+                docs.push(Doc::text(" "));
+                docs.push(self.query_partition(partition_by));
+            }
+            docs.push(Doc::HardLine);
+            docs.push(self.join_clause(clause));
+        }
+        Doc::Concat(docs)
+    }
+
+    fn join_clause(&mut self, clause: &JoinClause) -> Doc {
+        let (keywords, table, spellings) = match clause {
+            JoinClause::InnerJoin(InnerJoin {
+                inner_type,
+                inner,
+                join_table,
+                spellings,
+                ..
+            }) => {
+                let mut keywords = match inner_type {
+                    InnerJoinType::Inner => vec![],
+                    InnerJoinType::Cross => vec![Keyword::Cross],
+                    InnerJoinType::Natural => vec![Keyword::Natural],
+                };
+                if *inner {
+                    keywords.push(Keyword::Inner);
+                }
+                keywords.push(Keyword::Join);
+                (keywords, join_table, spellings)
+            }
+            JoinClause::OuterJoin(outer_join) => {
+                let mut keywords = match outer_join.natural {
+                    true => vec![Keyword::Natural],
+                    false => vec![],
+                };
+                keywords.push(match outer_join.outer_type {
+                    OuterJoinType::Left => Keyword::Left,
+                    OuterJoinType::Right => Keyword::Right,
+                    OuterJoinType::Full => Keyword::Full,
+                });
+                if outer_join.outer {
+                    keywords.push(Keyword::Outer);
+                }
+                keywords.push(Keyword::Join);
+                (keywords, &outer_join.join_table, &outer_join.spellings)
+            }
+            JoinClause::Apply(Apply {
+                apply_type,
+                table,
+                spellings,
+            }) => {
+                let keyword = match apply_type {
+                    ApplyType::Cross => Keyword::Cross,
+                    ApplyType::Outer => Keyword::Outer,
+                };
+                (vec![keyword, Keyword::Apply], table, spellings)
+            }
+        };
+
+        let mut docs = vec![self.leading_comments(&table.comments().leading)];
+        let mut spellings = spellings.iter();
+        for keyword in keywords {
+            docs.push(self.keyword(keyword, spellings.next()));
+            docs.push(Doc::text(" "));
+        }
+        docs.push(self.table_reference(table));
+        if let JoinClause::OuterJoin(OuterJoin {
+            join_partition_by: Some(join_partition_by),
+            ..
+        }) = clause
+        {
+            docs.push(Doc::text(" "));
+            docs.push(self.query_partition(join_partition_by));
+        }
+        docs.push(self.trailing_comments(&table.comments().trailing));
+
+        let condition = match clause {
+            JoinClause::InnerJoin(InnerJoin { condition, .. })
+            | JoinClause::OuterJoin(OuterJoin { condition, .. }) => condition.as_ref(),
+            JoinClause::Apply(_) => None,
+        };
+        if let Some(condition) = condition {
+            let condition = self.join_condition(condition);
+            docs.push(Doc::nest(Doc::Concat(vec![Doc::HardLine, condition])));
+        }
+        Doc::Concat(docs)
+    }
+
+    fn join_condition(&mut self, condition: &JoinCondition) -> Doc {
+        match condition {
+            JoinCondition::On(condition, spellings) => Doc::Concat(vec![
+                self.keyword(Keyword::On, spellings.iter().next()),
+                Doc::text(" "),
+                self.expression(condition),
+            ]),
+            JoinCondition::Using(columns, spellings) => Doc::Concat(vec![
+                self.keyword(Keyword::Using, spellings.iter().next()),
+                Doc::text(" "),
+                Doc::parenthesized(
+                    columns
+                        .iter()
+                        .map(|column| self.identifier(column))
+                        .collect(),
+                ),
+            ]),
+        }
+    }
+
+    fn query_partition(&mut self, partition: &QueryPartition) -> Doc {
+        let mut spellings = partition.spellings.iter();
+        Doc::Concat(vec![
+            self.keyword(Keyword::Partition, spellings.next()),
+            Doc::text(" "),
+            self.keyword(Keyword::By, spellings.next()),
+            Doc::text(" "),
+            self.expression_list(&partition.expressions),
+        ])
+    }
+
+    fn table_reference(&mut self, table_reference: &TableReference) -> Doc {
+        match table_reference {
+            TableReference::QueryTableExpression(table) => self.query_table_expression(table),
+        }
+    }
+
+    fn query_table_expression(&mut self, table: &QueryTableExpression) -> Doc {
+        let mut docs = vec![];
+        if let Some(schema) = &table.schema {
+            docs.push(self.identifier(schema));
+            docs.push(Doc::text("."));
+        }
+        if let Some(name) = &table.table {
+            docs.push(self.identifier(name));
+        }
+        if let Some(db_link) = &table.db_link {
+            docs.push(Doc::text("@"));
+            for (i, name) in db_link.names.iter().enumerate() {
+                if i > 0 {
+                    docs.push(Doc::text("."));
+                }
+                docs.push(self.identifier(name));
+            }
+        }
+        if let Some(subquery) = &table.subquery {
+            docs.push(self.subquery(subquery));
+        }
+        if let Some(collection) = &table.collection {
+            docs.push(self.keyword(Keyword::Table, table.spellings.iter().next()));
+            docs.push(Doc::text("("));
+            docs.push(self.expression(collection));
+            docs.push(Doc::text(")"));
+        }
+        // Oracle does not accept AS before a table alias.
+        if let Some(alias) = &table.alias {
+            docs.push(Doc::text(" "));
+            docs.push(self.identifier(alias));
+        }
+        Doc::Concat(docs)
+    }
+}
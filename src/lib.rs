@@ -1,2 +1,5 @@
+pub mod config;
+pub mod diff;
+pub mod format;
 pub mod parser;
 pub mod tokenizer;
@@ -1,17 +1,386 @@
-use lafmt::parser;
+use lafmt::config::Config;
+use lafmt::diff::unified_diff;
+use lafmt::format::{self, FormatOptions};
 
-use std::{fs::File, io::Read};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::{env, fs};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let file = File::open("./tests/001.sql").expect("File not fount");
-    let mut f = file;
-    let mut query: String = String::new();
-    f.read_to_string(&mut query)?;
-    let mut p = parser::Parser::new();
-    let kek = p.parse(&query);
-    println!("{:?}", kek);
+const USAGE: &str = "\
+Usage: lafmt [OPTIONS] [FILES...]
 
-    println!("{}", &query);
+Formats Oracle SQL. Reads stdin when no FILES are given or when FILE is `-`.
+Directories are searched recursively for *.sql files.
 
+Settings come from the nearest lafmt.toml or .lafmt.toml in the directory of
+each file or in one of its parents (the current directory for stdin). Options
+given on the command line take precedence over the file.
+
+Options:
+    -w, --write             Rewrite files in place instead of printing to stdout
+        --check             List files that are not formatted and exit with 1
+        --diff              Like --check, but print a unified diff of the changes
+        --config <FILE>     Read settings from FILE instead of looking for lafmt.toml
+        --indent-width <N>  Number of spaces per indentation level [default: 4]
+        --max-width <N>     Line width before clauses and lists are broken over
+                            several lines [default: 100]
+        --keyword-case <CASE>
+                            upper, lower or preserve [default: upper]
+        --identifier-case <CASE>
+                            Case of unquoted names: upper, lower or preserve
+                            [default: preserve]
+        --comma-style <STYLE>
+                            Where commas go in broken lists: trailing or leading
+                            [default: trailing]
+    -h, --help              Print this message and exit
+";
+
+#[derive(Debug)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Input::Stdin => "<stdin>".to_string(),
+            Input::File(path) => path.display().to_string(),
+        }
+    }
+
+    fn read(&self) -> io::Result<String> {
+        match self {
+            Input::Stdin => {
+                let mut source = String::new();
+                io::stdin().read_to_string(&mut source)?;
+                Ok(source)
+            }
+            Input::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Stdout,
+    Write,
+    Check,
+    Diff,
+}
+
+#[derive(Debug)]
+struct Args {
+    inputs: Vec<Input>,
+    mode: Mode,
+    config: Option<PathBuf>,
+    // Settings given on the command line
+    overrides: Config,
+}
+
+const SQL_EXTENSIONS: [&str; 1] = ["sql"];
+const DIFF_CONTEXT: usize = 3;
+
+fn parse_args<I>(args: I) -> Result<Option<Args>, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut inputs = vec![];
+    let mut mode = Mode::Stdout;
+    let mut config = None;
+    let mut overrides = Config::default();
+    let mut only_files = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            _ if only_files => inputs.push(Input::File(PathBuf::from(arg))),
+            "--" => only_files = true,
+            "-h" | "--help" => return Ok(None),
+            "-w" | "--write" if mode != Mode::Stdout => {
+                return Err("--write cannot be combined with --check or --diff".to_string())
+            }
+            "--check" | "--diff" if mode == Mode::Write => {
+                return Err("--write cannot be combined with --check or --diff".to_string())
+            }
+            "-w" | "--write" => mode = Mode::Write,
+            "--check" if mode == Mode::Diff => {}
+            "--check" => mode = Mode::Check,
+            "--diff" => mode = Mode::Diff,
+            "--config" => config = Some(option_value(&mut args, &arg)?),
+            "--indent-width" => overrides.indent_width = Some(option_value(&mut args, &arg)?),
+            "--max-width" => overrides.max_width = Some(option_value(&mut args, &arg)?),
+            "--comma-style" => overrides.comma_style = Some(option_value(&mut args, &arg)?),
+            "--keyword-case" => overrides.keyword_case = Some(option_value(&mut args, &arg)?),
+            "--identifier-case" => overrides.identifier_case = Some(option_value(&mut args, &arg)?),
+            "-" => inputs.push(Input::Stdin),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ => inputs.push(Input::File(PathBuf::from(arg))),
+        }
+    }
+
+    if inputs.is_empty() {
+        inputs.push(Input::Stdin);
+    }
+    if mode == Mode::Write && inputs.iter().any(|input| matches!(input, Input::Stdin)) {
+        return Err("--write cannot be used with stdin".to_string());
+    }
+
+    Ok(Some(Args {
+        inputs,
+        mode,
+        config,
+        overrides,
+    }))
+}
+
+/// Parses the value that follows `option`.
+fn option_value<T: FromStr>(
+    args: &mut impl Iterator<Item = String>,
+    option: &str,
+) -> Result<T, String> {
+    let value = args.next().ok_or(format!("{} expects a value", option))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid {} '{}'", option, value))
+}
+
+/// Defaults, overridden by the config file that applies to `input`, overridden by the command
+/// line.
+fn options_for(input: &Input, args: &Args) -> Result<FormatOptions, String> {
+    let path = match (&args.config, input) {
+        (Some(path), _) => Some(path.clone()),
+        (None, Input::File(path)) => std::path::absolute(path)
+            .map(|path| Config::find(&path))
+            .map_err(|e| format!("{}: {}", input.name(), e))?,
+        (None, Input::Stdin) => env::current_dir()
+            .map(|dir| Config::find(&dir))
+            .map_err(|e| format!("{}: {}", input.name(), e))?,
+    };
+
+    let mut opts = FormatOptions::default();
+    if let Some(path) = path {
+        Config::load(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .apply(&mut opts);
+    }
+    args.overrides.apply(&mut opts);
+    Ok(opts)
+}
+
+/// Replaces directories with the SQL files found beneath them, in a stable order.
+fn expand_inputs(inputs: &[Input]) -> Result<Vec<Input>, String> {
+    let mut expanded = vec![];
+    for input in inputs {
+        match input {
+            Input::File(path) if path.is_dir() => {
+                let mut files = vec![];
+                collect_sql_files(path, &mut files)
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+                files.sort();
+                expanded.extend(files.into_iter().map(Input::File));
+            }
+            Input::File(path) => expanded.push(Input::File(path.clone())),
+            Input::Stdin => expanded.push(Input::Stdin),
+        }
+    }
+    Ok(expanded)
+}
+
+fn collect_sql_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_sql_files(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SQL_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        {
+            files.push(path);
+        }
+    }
     Ok(())
 }
+
+/// Formats one input and returns whether its contents were already formatted.
+fn run(input: &Input, args: &Args) -> Result<bool, String> {
+    let source = input
+        .read()
+        .map_err(|e| format!("{}: {}", input.name(), e))?;
+    let opts = options_for(input, args)?;
+    let formatted =
+        format::format_source(&source, &opts).map_err(|e| format!("{}: {}", input.name(), e))?;
+
+    let formatted_already = formatted == source;
+
+    match (&args.mode, input) {
+        (Mode::Write, Input::File(path)) => {
+            if !formatted_already {
+                fs::write(path, formatted).map_err(|e| format!("{}: {}", input.name(), e))?;
+            }
+        }
+        (Mode::Check, _) => {
+            if !formatted_already {
+                println!("{}", input.name());
+            }
+        }
+        (Mode::Diff, _) => {
+            let name = input.name();
+            let diff = unified_diff(
+                &source,
+                &formatted,
+                &format!("a/{}", name),
+                &format!("b/{}", name),
+                DIFF_CONTEXT,
+            );
+            io::stdout()
+                .write_all(diff.as_bytes())
+                .map_err(|e| format!("<stdout>: {}", e))?;
+        }
+        _ => {
+            io::stdout()
+                .write_all(formatted.as_bytes())
+                .map_err(|e| format!("<stdout>: {}", e))?;
+        }
+    }
+    Ok(formatted_already)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("lafmt: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let inputs = match expand_inputs(&args.inputs) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("lafmt: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    for input in &inputs {
+        match run(input, &args) {
+            Ok(false) if matches!(args.mode, Mode::Check | Mode::Diff) => {
+                status = ExitCode::FAILURE
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("lafmt: {}", e);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_args, Input, Mode};
+    use lafmt::format::{Case, CommaStyle};
+    use std::path::PathBuf;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    pub fn test_parse_args_defaults_to_stdin() {
+        let parsed = parse_args(args(&[])).unwrap().unwrap();
+
+        assert_eq!(Mode::Stdout, parsed.mode);
+        assert!(matches!(parsed.inputs[..], [Input::Stdin]));
+    }
+
+    #[test]
+    pub fn test_parse_args_files() {
+        let parsed = parse_args(args(&["--write", "a.sql", "--", "-b.sql"]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(Mode::Write, parsed.mode);
+        assert_eq!(None, parsed.overrides.indent_width);
+        let names: Vec<String> = parsed.inputs.iter().map(Input::name).collect();
+        assert_eq!(vec!["a.sql", "-b.sql"], names);
+    }
+
+    #[test]
+    pub fn test_parse_args_check_and_diff() {
+        let parsed = parse_args(args(&["--check", "--diff", "a.sql"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(Mode::Diff, parsed.mode);
+
+        let parsed = parse_args(args(&["--diff", "--check"])).unwrap().unwrap();
+        assert_eq!(Mode::Diff, parsed.mode);
+    }
+
+    #[test]
+    pub fn test_parse_args_config() {
+        let parsed = parse_args(args(&[
+            "--config",
+            "team.toml",
+            "--indent-width",
+            "2",
+            "--max-width",
+            "80",
+        ]))
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(Some(PathBuf::from("team.toml")), parsed.config);
+        assert_eq!(Some(2), parsed.overrides.indent_width);
+        assert_eq!(Some(80), parsed.overrides.max_width);
+    }
+
+    #[test]
+    pub fn test_parse_args_case() {
+        let parsed = parse_args(args(&[
+            "--keyword-case",
+            "lower",
+            "--identifier-case",
+            "upper",
+        ]))
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(Some(Case::Lower), parsed.overrides.keyword_case);
+        assert_eq!(Some(Case::Upper), parsed.overrides.identifier_case);
+        assert!(parse_args(args(&["--keyword-case", "title"])).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_args_comma_style() {
+        let parsed = parse_args(args(&["--comma-style", "leading"]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(Some(CommaStyle::Leading), parsed.overrides.comma_style);
+        assert!(parse_args(args(&["--comma-style", "first"])).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_args_errors() {
+        assert!(parse_args(args(&["--nope"])).is_err());
+        assert!(parse_args(args(&["--indent-width", "x"])).is_err());
+        assert!(parse_args(args(&["--indent-width"])).is_err());
+        assert!(parse_args(args(&["-w", "-"])).is_err());
+        assert!(parse_args(args(&["--check", "--write"])).is_err());
+        assert!(parse_args(args(&["--diff", "-w"])).is_err());
+        assert!(parse_args(args(&["-w", "--diff"])).is_err());
+        assert!(parse_args(args(&["--config"])).is_err());
+        assert!(parse_args(args(&["-h"])).unwrap().is_none());
+    }
+}
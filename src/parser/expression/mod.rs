@@ -1,11 +1,1095 @@
+use crate::parser::statement::query::{
+    consume_keyword, expect_keyword, expect_rparen, parse_identifier, parse_subquery,
+    DMLParseError, Distinc, SubQuery,
+};
+use crate::parser::{Comments, Identifier, Parser, Spellings};
+use crate::tokenizer::{keyword::Keyword, Comment, Token};
+
 #[derive(Debug)]
 pub enum Expression {
     SimpleExpression(SimpleExpression),
+    // `:name`, printed as written
+    BindVariable(String),
+    // `&name`, printed as written
+    SubstitutionVariable(String),
+    // Number or string literal as written
+    Literal(String),
+    // `DATE '2024-01-31'` or `TIMESTAMP '...'`
+    TypedLiteral(Keyword, String, Spellings),
+    Null(Spellings),
+    Unary(UnaryExpression),
+    Binary(BinaryExpression),
+    IsNull(IsNullExpression),
+    In(InExpression),
+    Between(BetweenExpression),
+    Like(LikeExpression),
+    Exists(Box<SubQuery>, Spellings),
+    // `ANY (...)`, `SOME (...)` or `ALL (...)` on the right of a comparison
+    Quantified(QuantifiedExpression),
+    // A parenthesized query used as a value
+    Subquery(Box<SubQuery>),
+    Parenthesized(Box<Expression>),
+    // `(a, b)`, as in `(a, b) IN ((1, 2))`
+    ExpressionList(Vec<Expression>),
+    FunctionCall(FunctionCall),
+    Case(CaseExpression),
+    Cast(CastExpression),
+    // An operand with the comments standing around it
+    Commented(CommentedExpression),
+}
+
+impl Expression {
+    /// The expression with `comments` around it, unwrapped when there are none.
+    fn with_comments(self, comments: Comments) -> Expression {
+        if comments.leading.is_empty() && comments.trailing.is_empty() {
+            return self;
+        }
+        match self {
+            Expression::Commented(mut commented) => {
+                let mut leading = comments.leading;
+                leading.append(&mut commented.comments.leading);
+                commented.comments.leading = leading;
+                commented.comments.trailing.extend(comments.trailing);
+                Expression::Commented(commented)
+            }
+            expression => Expression::Commented(CommentedExpression {
+                expression: Box::new(expression),
+                comments,
+            }),
+        }
+    }
+
+    /// Adds `trailing` to the comments at the end of the expression's last line.
+    fn push_trailing_comments(&mut self, trailing: Vec<Comment>) {
+        let expression = std::mem::replace(self, Expression::Null(Spellings::default()));
+        *self = expression.with_comments(Comments {
+            leading: vec![],
+            trailing,
+        });
+    }
 }
 
 #[derive(Debug)]
 pub struct SimpleExpression {
-    pub column: String,
-    pub schema: Option<String>,
-    pub table: Option<String>,
+    pub column: Identifier,
+    pub schema: Option<Identifier>,
+    pub table: Option<Identifier>,
+    // Oracle's outer join operator `(+)` after the column
+    pub outer_join: bool,
+}
+
+/// `nvl(a, 0)`, `count(*)`, `count(DISTINCT a)` or `schema.package.function(...)`.
+#[derive(Debug)]
+pub struct FunctionCall {
+    pub names: Vec<Identifier>,
+    pub distinct: Distinc,
+    pub arguments: Vec<Expression>,
+    pub spellings: Spellings,
+}
+
+/// A simple CASE has an operand compared to every WHEN value, a searched CASE has a
+/// condition in every WHEN.
+#[derive(Debug)]
+pub struct CaseExpression {
+    pub operand: Option<Box<Expression>>,
+    pub when_clauses: Vec<WhenClause>,
+    pub else_result: Option<Box<Expression>>,
+    // CASE, ELSE and END
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct WhenClause {
+    pub condition: Expression,
+    pub result: Expression,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct CastExpression {
+    pub expression: Box<Expression>,
+    pub data_type: DataType,
+    pub spellings: Spellings,
+}
+
+/// A data type as written, `NUMBER(10, 2)`, `VARCHAR2(20 CHAR)`, `TIMESTAMP(3) WITH TIME ZONE`
+/// or `hr.address_t`.
+#[derive(Debug)]
+pub struct DataType {
+    pub parts: Vec<DataTypePart>,
+    // Of the keyword parts
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub enum DataTypePart {
+    Keyword(Keyword),
+    Name(Identifier),
+    // A precision, scale or length, or `*`
+    Value(String),
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+}
+
+#[derive(Debug)]
+pub struct CommentedExpression {
+    pub expression: Box<Expression>,
+    pub comments: Comments,
+}
+
+#[derive(Debug)]
+pub struct UnaryExpression {
+    pub operator: UnaryOperator,
+    pub operand: Box<Expression>,
+    // NOT or PRIOR
+    pub spellings: Spellings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    Not,
+    Prior,
+}
+
+#[derive(Debug)]
+pub struct BinaryExpression {
+    pub left: Box<Expression>,
+    pub operator: BinaryOperator,
+    pub right: Box<Expression>,
+    // AND or OR
+    pub spellings: Spellings,
+}
+
+/// The inequality operators keep their spelling, `<>`, `!=`, `^=` and `~=` mean the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Concat,
+    Eq,
+    Neq,
+    BangEq,
+    CaretEq,
+    TildeEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+}
+
+impl BinaryOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Sub => "-",
+            BinaryOperator::Mul => "*",
+            BinaryOperator::Div => "/",
+            BinaryOperator::Concat => "||",
+            BinaryOperator::Eq => "=",
+            BinaryOperator::Neq => "<>",
+            BinaryOperator::BangEq => "!=",
+            BinaryOperator::CaretEq => "^=",
+            BinaryOperator::TildeEq => "~=",
+            BinaryOperator::Lt => "<",
+            BinaryOperator::LtEq => "<=",
+            BinaryOperator::Gt => ">",
+            BinaryOperator::GtEq => ">=",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Or => "OR",
+        }
+    }
+
+    fn binding_power(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => OR,
+            BinaryOperator::And => AND,
+            BinaryOperator::Add | BinaryOperator::Sub | BinaryOperator::Concat => ADDITIVE,
+            BinaryOperator::Mul | BinaryOperator::Div => MULTIPLICATIVE,
+            _ => COMPARISON,
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        self.binding_power() == COMPARISON
+    }
+}
+
+#[derive(Debug)]
+pub struct IsNullExpression {
+    pub expression: Box<Expression>,
+    pub not: bool,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct InExpression {
+    pub expression: Box<Expression>,
+    pub not: bool,
+    // A parenthesized list or a subquery
+    pub list: Box<Expression>,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct BetweenExpression {
+    pub expression: Box<Expression>,
+    pub not: bool,
+    pub low: Box<Expression>,
+    pub high: Box<Expression>,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct LikeExpression {
+    pub expression: Box<Expression>,
+    pub not: bool,
+    pub pattern: Box<Expression>,
+    pub escape: Option<Box<Expression>>,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct QuantifiedExpression {
+    pub quantifier: Quantifier,
+    // A parenthesized list or a subquery
+    pub list: Box<Expression>,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantifier {
+    Any,
+    Some,
+    All,
+}
+
+// Binding powers, loosest first, after Oracle's operator and condition precedence: the
+// arithmetic operators bind tighter than the conditions, which bind tighter than NOT, AND
+// and OR.
+const OR: u8 = 1;
+const AND: u8 = 2;
+const NOT: u8 = 3;
+const COMPARISON: u8 = 4;
+const ADDITIVE: u8 = 5;
+const MULTIPLICATIVE: u8 = 6;
+const UNARY: u8 = 7;
+
+enum Infix {
+    Binary(BinaryOperator),
+    IsNull,
+    In { not: bool },
+    Between { not: bool },
+    Like { not: bool },
+}
+
+pub fn parse_expression(p: &mut Parser) -> Result<Expression, DMLParseError> {
+    parse_expression_bp(p, 0)
+}
+
+/// Parses an expression with the comments standing before it, `leading` first, and those
+/// following it on its last line.
+pub(crate) fn parse_commented_expression(
+    p: &mut Parser,
+    leading: Vec<Comment>,
+) -> Result<Expression, DMLParseError> {
+    parse_commented_expression_bp(p, leading, 0)
+}
+
+fn parse_commented_expression_bp(
+    p: &mut Parser,
+    mut leading: Vec<Comment>,
+    min_bp: u8,
+) -> Result<Expression, DMLParseError> {
+    leading.extend(p.take_leading_comments());
+    let expression = parse_expression_bp(p, min_bp)?;
+    let trailing = p.take_trailing_comments();
+    Ok(expression.with_comments(Comments { leading, trailing }))
+}
+
+/// Parses operators binding tighter than `min_bp`, leaving the rest to the caller.
+fn parse_expression_bp(p: &mut Parser, min_bp: u8) -> Result<Expression, DMLParseError> {
+    let mut left = parse_prefix(p)?;
+    while let Some(infix) = peek_infix(p) {
+        let bp = match &infix {
+            Infix::Binary(operator) => operator.binding_power(),
+            _ => COMPARISON,
+        };
+        if bp <= min_bp {
+            break;
+        }
+        left = parse_infix(p, left, infix, bp)?;
+    }
+    Ok(left)
+}
+
+fn parse_prefix(p: &mut Parser) -> Result<Expression, DMLParseError> {
+    let mut spellings = Spellings::default();
+    let unary = match p.peek_token() {
+        Some(Token::NumberLiteral(literal)) | Some(Token::StringLiteral(literal)) => {
+            let expression = Expression::Literal(literal.to_string());
+            let _ = p.token_next();
+            return Ok(expression);
+        }
+        Some(Token::BindVariable(name)) => {
+            let expression = Expression::BindVariable(name.to_string());
+            let _ = p.token_next();
+            return Ok(expression);
+        }
+        Some(Token::SubstitutionVariable(name)) => {
+            let expression = Expression::SubstitutionVariable(name.to_string());
+            let _ = p.token_next();
+            return Ok(expression);
+        }
+        Some(Token::Keyword(Keyword::Null)) => {
+            p.keyword_next(&mut spellings);
+            return Ok(Expression::Null(spellings));
+        }
+        Some(Token::Keyword(keyword @ (Keyword::Date | Keyword::Timestamp))) => {
+            match p.peek_nth(1) {
+                Some(Token::StringLiteral(literal)) => {
+                    let (keyword, literal) = (*keyword, literal.to_string());
+                    p.keyword_next(&mut spellings);
+                    let _ = p.token_next();
+                    return Ok(Expression::TypedLiteral(keyword, literal, spellings));
+                }
+                _ => None,
+            }
+        }
+        Some(Token::Keyword(Keyword::Exists)) => {
+            p.keyword_next(&mut spellings);
+            let subquery = parse_subquery_operand(p)?;
+            return Ok(Expression::Exists(Box::new(subquery), spellings));
+        }
+        Some(Token::LParen) => return parse_parenthesized(p),
+        Some(Token::Keyword(Keyword::Case)) => return parse_case(p),
+        Some(Token::Keyword(Keyword::Cast)) if p.peek_nth(1) == Some(&Token::LParen) => {
+            return parse_cast(p)
+        }
+        Some(Token::Plus) => Some((UnaryOperator::Plus, UNARY)),
+        Some(Token::Minus) => Some((UnaryOperator::Minus, UNARY)),
+        Some(Token::Keyword(Keyword::Prior)) => Some((UnaryOperator::Prior, UNARY)),
+        Some(Token::Keyword(Keyword::Not)) => Some((UnaryOperator::Not, NOT)),
+        _ => None,
+    };
+    match unary {
+        Some((operator, bp)) => {
+            match operator {
+                UnaryOperator::Not | UnaryOperator::Prior => p.keyword_next(&mut spellings),
+                UnaryOperator::Plus | UnaryOperator::Minus => {
+                    let _ = p.token_next();
+                }
+            }
+            let operand = parse_expression_bp(p, bp)?;
+            Ok(Expression::Unary(UnaryExpression {
+                operator,
+                operand: Box::new(operand),
+                spellings,
+            }))
+        }
+        None => parse_name_expression(p),
+    }
+}
+
+fn peek_infix(p: &Parser) -> Option<Infix> {
+    let operator = match p.peek_token()? {
+        Token::Plus => BinaryOperator::Add,
+        Token::Minus => BinaryOperator::Sub,
+        Token::Asterisk(_) => BinaryOperator::Mul,
+        Token::Slash => BinaryOperator::Div,
+        Token::Concat => BinaryOperator::Concat,
+        Token::Eq => BinaryOperator::Eq,
+        Token::Neq => BinaryOperator::Neq,
+        Token::BangEq => BinaryOperator::BangEq,
+        Token::CaretEq => BinaryOperator::CaretEq,
+        Token::TildeEq => BinaryOperator::TildeEq,
+        Token::Lt => BinaryOperator::Lt,
+        Token::LtEq => BinaryOperator::LtEq,
+        Token::Gt => BinaryOperator::Gt,
+        Token::GtEq => BinaryOperator::GtEq,
+        Token::Keyword(Keyword::And) => BinaryOperator::And,
+        Token::Keyword(Keyword::Or) => BinaryOperator::Or,
+        Token::Keyword(Keyword::Is) => return Some(Infix::IsNull),
+        Token::Keyword(Keyword::In) => return Some(Infix::In { not: false }),
+        Token::Keyword(Keyword::Between) => return Some(Infix::Between { not: false }),
+        Token::Keyword(Keyword::Like) => return Some(Infix::Like { not: false }),
+        Token::Keyword(Keyword::Not) => {
+            return match p.peek_nth(1)? {
+                Token::Keyword(Keyword::In) => Some(Infix::In { not: true }),
+                Token::Keyword(Keyword::Between) => Some(Infix::Between { not: true }),
+                Token::Keyword(Keyword::Like) => Some(Infix::Like { not: true }),
+                _ => None,
+            }
+        }
+        _ => return None,
+    };
+    Some(Infix::Binary(operator))
+}
+
+fn parse_infix(
+    p: &mut Parser,
+    left: Expression,
+    infix: Infix,
+    bp: u8,
+) -> Result<Expression, DMLParseError> {
+    // Comments around AND and OR stay with the operands: those ending the line of the left
+    // one are its own, the ones after it go above the right one.
+    let logical = matches!(
+        infix,
+        Infix::Binary(BinaryOperator::And | BinaryOperator::Or)
+    );
+    let mut leading = vec![];
+    let left = match logical {
+        true => {
+            let trailing = p.take_trailing_comments();
+            leading = p.take_leading_comments();
+            left.with_comments(Comments {
+                leading: vec![],
+                trailing,
+            })
+        }
+        false => left,
+    };
+    let left = Box::new(left);
+    let mut spellings = Spellings::default();
+    match infix {
+        Infix::Binary(_) if !logical => {
+            let _ = p.token_next();
+        }
+        _ => p.keyword_next(&mut spellings),
+    }
+    let expression = match infix {
+        Infix::Binary(operator) => {
+            let right = match parse_quantifier(p) {
+                Some((quantifier, spellings)) if operator.is_comparison() => {
+                    Expression::Quantified(QuantifiedExpression {
+                        quantifier,
+                        list: Box::new(parse_parenthesized(p)?),
+                        spellings,
+                    })
+                }
+                _ if logical => {
+                    leading.extend(p.take_trailing_comments());
+                    parse_commented_expression_bp(p, leading, bp)?
+                }
+                _ => parse_expression_bp(p, bp)?,
+            };
+            Expression::Binary(BinaryExpression {
+                left,
+                operator,
+                right: Box::new(right),
+                spellings,
+            })
+        }
+        Infix::IsNull => {
+            let not = consume_keyword(p, Keyword::Not, &mut spellings);
+            expect_keyword(
+                p,
+                Keyword::Null,
+                &mut spellings,
+                DMLParseError::ExpressionParseError,
+            )?;
+            Expression::IsNull(IsNullExpression {
+                expression: left,
+                not,
+                spellings,
+            })
+        }
+        Infix::In { not } => {
+            if not {
+                p.keyword_next(&mut spellings);
+            }
+            Expression::In(InExpression {
+                expression: left,
+                not,
+                list: Box::new(parse_parenthesized(p)?),
+                spellings,
+            })
+        }
+        Infix::Between { not } => {
+            if not {
+                p.keyword_next(&mut spellings);
+            }
+            // The bounds bind tighter than the AND between them.
+            let low = parse_expression_bp(p, bp)?;
+            expect_keyword(
+                p,
+                Keyword::And,
+                &mut spellings,
+                DMLParseError::ExpressionParseError,
+            )?;
+            let high = parse_expression_bp(p, bp)?;
+            Expression::Between(BetweenExpression {
+                expression: left,
+                not,
+                low: Box::new(low),
+                high: Box::new(high),
+                spellings,
+            })
+        }
+        Infix::Like { not } => {
+            if not {
+                p.keyword_next(&mut spellings);
+            }
+            let pattern = parse_expression_bp(p, bp)?;
+            let escape = match consume_keyword(p, Keyword::Escape, &mut spellings) {
+                true => Some(Box::new(parse_expression_bp(p, bp)?)),
+                false => None,
+            };
+            Expression::Like(LikeExpression {
+                expression: left,
+                not,
+                pattern: Box::new(pattern),
+                escape,
+                spellings,
+            })
+        }
+    };
+    Ok(expression)
+}
+
+/// Consumes ANY, SOME or ALL before a parenthesized list. SOME is not reserved and may also
+/// name a column.
+fn parse_quantifier(p: &mut Parser) -> Option<(Quantifier, Spellings)> {
+    let quantifier = match p.peek_token()? {
+        Token::Keyword(Keyword::Any) => Quantifier::Any,
+        Token::Keyword(Keyword::Some) => Quantifier::Some,
+        Token::Keyword(Keyword::All) => Quantifier::All,
+        _ => return None,
+    };
+    if p.peek_nth(1) != Some(&Token::LParen) {
+        return None;
+    }
+    let mut spellings = Spellings::default();
+    p.keyword_next(&mut spellings);
+    Some((quantifier, spellings))
+}
+
+/// A subquery, a single parenthesized expression or a list of them.
+fn parse_parenthesized(p: &mut Parser) -> Result<Expression, DMLParseError> {
+    if p.peek_token() != Some(&Token::LParen) {
+        return Err(DMLParseError::ExpressionParseError(format!(
+            "{}: Expected '(', found {:?}",
+            p.location(),
+            p.peek_token()
+        )));
+    }
+    if matches!(
+        p.peek_nth(1),
+        Some(Token::Keyword(Keyword::Select | Keyword::With))
+    ) {
+        return Ok(Expression::Subquery(Box::new(parse_subquery_operand(p)?)));
+    }
+    let _ = p.token_next();
+    let mut expressions = vec![parse_expression(p)?];
+    while p.peek_token() == Some(&Token::Comma) {
+        let _ = p.token_next();
+        expressions.push(parse_expression(p)?);
+    }
+    expect_rparen(p, DMLParseError::ExpressionParseError)?;
+    Ok(match expressions.len() {
+        1 => Expression::Parenthesized(Box::new(expressions.remove(0))),
+        _ => Expression::ExpressionList(expressions),
+    })
+}
+
+fn parse_subquery_operand(p: &mut Parser) -> Result<SubQuery, DMLParseError> {
+    match p.peek_token() {
+        Some(Token::LParen) => parse_subquery(p)?,
+        _ => None,
+    }
+    .ok_or_else(|| {
+        DMLParseError::ExpressionParseError(format!(
+            "{}: Expected a parenthesized subquery, found {:?}",
+            p.location(),
+            p.peek_token()
+        ))
+    })
+}
+
+fn parse_case(p: &mut Parser) -> Result<Expression, DMLParseError> {
+    let mut spellings = Spellings::default();
+    p.keyword_next(&mut spellings);
+    let operand = match p.peek_token() {
+        Some(Token::Keyword(Keyword::When)) => None,
+        _ => Some(Box::new(parse_expression(p)?)),
+    };
+    // Comments after CASE or a branch go above the next branch.
+    let mut leading = p.take_trailing_comments();
+    leading.extend(p.take_leading_comments());
+    let mut when_clauses = vec![];
+    while p.peek_token() == Some(&Token::Keyword(Keyword::When)) {
+        let mut when_spellings = Spellings::default();
+        p.keyword_next(&mut when_spellings);
+        leading.extend(p.take_trailing_comments());
+        let condition = parse_commented_expression(p, leading)?;
+        let mut leading_result = p.take_leading_comments();
+        expect_keyword(
+            p,
+            Keyword::Then,
+            &mut when_spellings,
+            DMLParseError::ExpressionParseError,
+        )?;
+        leading_result.extend(p.take_trailing_comments());
+        let result = parse_commented_expression(p, leading_result)?;
+        when_clauses.push(WhenClause {
+            condition,
+            result,
+            spellings: when_spellings,
+        });
+        leading = p.take_leading_comments();
+    }
+    if when_clauses.is_empty() {
+        return Err(DMLParseError::ExpressionParseError(format!(
+            "{}: Expected WHEN, found {:?}",
+            p.location(),
+            p.peek_token()
+        )));
+    }
+    let mut else_result = match consume_keyword(p, Keyword::Else, &mut spellings) {
+        true => {
+            leading.extend(p.take_trailing_comments());
+            let else_result = parse_commented_expression(p, std::mem::take(&mut leading))?;
+            leading = p.take_leading_comments();
+            Some(Box::new(else_result))
+        }
+        false => None,
+    };
+    // Comments before END stay on the line of the last branch.
+    let last_result = match &mut else_result {
+        Some(else_result) => Some(else_result.as_mut()),
+        None => when_clauses.last_mut().map(|when| &mut when.result),
+    };
+    if let Some(last_result) = last_result {
+        last_result.push_trailing_comments(leading);
+    }
+    expect_keyword(
+        p,
+        Keyword::End,
+        &mut spellings,
+        DMLParseError::ExpressionParseError,
+    )?;
+    Ok(Expression::Case(CaseExpression {
+        operand,
+        when_clauses,
+        else_result,
+        spellings,
+    }))
+}
+
+// CAST(expression AS data_type)
+fn parse_cast(p: &mut Parser) -> Result<Expression, DMLParseError> {
+    let mut spellings = Spellings::default();
+    p.keyword_next(&mut spellings);
+    let _ = p.token_next();
+    let expression = parse_expression(p)?;
+    expect_keyword(
+        p,
+        Keyword::As,
+        &mut spellings,
+        DMLParseError::ExpressionParseError,
+    )?;
+    let data_type = parse_data_type(p)?;
+    expect_rparen(p, DMLParseError::ExpressionParseError)?;
+    Ok(Expression::Cast(CastExpression {
+        expression: Box::new(expression),
+        data_type,
+        spellings,
+    }))
+}
+
+/// Reads the data type up to the closing parenthesis of CAST.
+fn parse_data_type(p: &mut Parser) -> Result<DataType, DMLParseError> {
+    let mut parts = vec![];
+    let mut spellings = Spellings::default();
+    let mut depth = 0;
+    loop {
+        let part = match p.peek_token() {
+            Some(Token::RParen) if depth == 0 => break,
+            Some(Token::RParen) => {
+                depth -= 1;
+                DataTypePart::RParen
+            }
+            Some(Token::LParen) => {
+                depth += 1;
+                DataTypePart::LParen
+            }
+            Some(Token::Comma) if depth > 0 => DataTypePart::Comma,
+            Some(Token::Dot) => DataTypePart::Dot,
+            Some(Token::NumberLiteral(value)) => DataTypePart::Value(value.to_string()),
+            Some(Token::Asterisk(_)) => DataTypePart::Value("*".to_string()),
+            // Words like TIME or ZONE are keywords here whatever else they may name.
+            Some(Token::Keyword(keyword)) => {
+                parts.push(DataTypePart::Keyword(*keyword));
+                p.keyword_next(&mut spellings);
+                continue;
+            }
+            _ => match parse_identifier(p) {
+                Some(name) => {
+                    parts.push(DataTypePart::Name(name));
+                    continue;
+                }
+                None => {
+                    return Err(DMLParseError::ExpressionParseError(format!(
+                        "{}: Unexpected {:?} in data type",
+                        p.location(),
+                        p.peek_token()
+                    )))
+                }
+            },
+        };
+        let _ = p.token_next();
+        parts.push(part);
+    }
+    if parts.is_empty() {
+        return Err(DMLParseError::ExpressionParseError(format!(
+            "{}: Expected a data type, found {:?}",
+            p.location(),
+            p.peek_token()
+        )));
+    }
+    Ok(DataType { parts, spellings })
+}
+
+/// A column `[schema.]table.column`, `table.*` or a function call `[schema.][package.]f(...)`.
+fn parse_name_expression(p: &mut Parser) -> Result<Expression, DMLParseError> {
+    let mut names = vec![];
+    loop {
+        if let Some(Token::Asterisk(_)) = p.peek_token() {
+            let _ = p.token_next();
+            names.push(Identifier::Bare("*".to_string()));
+        } else if let Some(name) = parse_identifier(p) {
+            names.push(name);
+        } else if matches!(p.peek_token(), Some(Token::Keyword(k)) if k.is_pseudocolumn()) {
+            names.push(Identifier::Bare(p.peek_text().to_string()));
+            let _ = p.token_next();
+        } else {
+            return Err(DMLParseError::ExpressionParseError(format!(
+                "{}: Expected an expression, found {:?}",
+                p.location(),
+                p.peek_token()
+            )));
+        }
+        if names.len() == 3 || names[names.len() - 1].text() == "*" {
+            break;
+        }
+        if p.peek_token() != Some(&Token::Dot) {
+            break;
+        }
+        let _ = p.token_next();
+    }
+
+    let outer_join = p.peek_token() == Some(&Token::LParen)
+        && p.peek_nth(1) == Some(&Token::Plus)
+        && p.peek_nth(2) == Some(&Token::RParen);
+    if outer_join {
+        p.move_index(3);
+    } else if p.peek_token() == Some(&Token::LParen) && names[names.len() - 1].text() != "*" {
+        let mut spellings = Spellings::default();
+        let (distinct, arguments) = parse_arguments(p, &mut spellings)?;
+        return Ok(Expression::FunctionCall(FunctionCall {
+            names,
+            distinct,
+            arguments,
+            spellings,
+        }));
+    }
+
+    let column = names.pop().unwrap_or(Identifier::Bare(String::new()));
+    let table = names.pop();
+    let schema = names.pop();
+    Ok(Expression::SimpleExpression(SimpleExpression {
+        column,
+        schema,
+        table,
+        outer_join,
+    }))
+}
+
+// ([DISTINCT | UNIQUE | ALL] argument, ...), where an argument may also be a query as in
+// MULTISET(SELECT ...)
+fn parse_arguments(
+    p: &mut Parser,
+    spellings: &mut Spellings,
+) -> Result<(Distinc, Vec<Expression>), DMLParseError> {
+    let _ = p.token_next();
+    let mut arguments = vec![];
+    if p.peek_token() == Some(&Token::RParen) {
+        let _ = p.token_next();
+        return Ok((Distinc::All, arguments));
+    }
+    let distinct = match p.peek_token() {
+        Some(Token::Keyword(Keyword::Distinct)) => Distinc::Distinct,
+        Some(Token::Keyword(Keyword::Unique)) => Distinc::Unique,
+        _ => Distinc::All,
+    };
+    match p.peek_token() {
+        // ALL is the default and is not printed.
+        Some(Token::Keyword(Keyword::All)) => {
+            let _ = p.token_next();
+        }
+        _ if !matches!(distinct, Distinc::All) => p.keyword_next(spellings),
+        _ => {}
+    }
+    loop {
+        // Comments after the parenthesis or a comma go above the argument, the ones after its
+        // comma stay on its line.
+        let mut leading = p.take_trailing_comments();
+        leading.extend(p.take_leading_comments());
+        let argument = match p.peek_token() {
+            Some(Token::Keyword(Keyword::Select | Keyword::With)) => parse_subquery(p)?,
+            _ => None,
+        };
+        let argument = match argument {
+            Some(subquery) => Expression::Subquery(Box::new(subquery)),
+            None => parse_expression(p)?,
+        };
+        let mut trailing = p.take_trailing_comments();
+        let comma = p.peek_token() == Some(&Token::Comma);
+        if comma {
+            let _ = p.token_next();
+            trailing.extend(p.take_trailing_comments());
+        }
+        arguments.push(argument.with_comments(Comments { leading, trailing }));
+        if !comma {
+            break;
+        }
+    }
+    expect_rparen(p, DMLParseError::ExpressionParseError)?;
+    Ok((distinct, arguments))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DataTypePart, Expression, Quantifier, UnaryOperator};
+    use crate::parser::statement::query::Distinc;
+    use crate::parser::statement::Statement;
+    use crate::parser::Parser;
+
+    // The select item of `select <expression> from dual` with every operation parenthesized.
+    fn parse_tree(expression: &str) -> String {
+        let statements = Parser::new()
+            .parse(&format!("select {} from dual", expression))
+            .unwrap();
+        let Statement::Query(subquery) = &statements[0] else {
+            panic!("Expected a query");
+        };
+        let query_block = subquery.query_block.as_ref().unwrap();
+        tree(&query_block.select_list[0].expression)
+    }
+
+    fn tree(expression: &Expression) -> String {
+        match expression {
+            Expression::SimpleExpression(simple) => simple.column.to_string(),
+            Expression::Literal(text) => text.to_string(),
+            Expression::Null(_) => "NULL".to_string(),
+            Expression::Unary(unary) => {
+                let operator = match unary.operator {
+                    UnaryOperator::Plus => "+",
+                    UnaryOperator::Minus => "-",
+                    UnaryOperator::Not => "NOT ",
+                    UnaryOperator::Prior => "PRIOR ",
+                };
+                format!("({}{})", operator, tree(&unary.operand))
+            }
+            Expression::Binary(binary) => format!(
+                "({} {} {})",
+                tree(&binary.left),
+                binary.operator.as_str(),
+                tree(&binary.right)
+            ),
+            Expression::IsNull(is_null) => format!(
+                "({} IS {}NULL)",
+                tree(&is_null.expression),
+                not(is_null.not)
+            ),
+            Expression::In(in_list) => format!(
+                "({} {}IN {})",
+                tree(&in_list.expression),
+                not(in_list.not),
+                tree(&in_list.list)
+            ),
+            Expression::Between(between) => format!(
+                "({} {}BETWEEN {} AND {})",
+                tree(&between.expression),
+                not(between.not),
+                tree(&between.low),
+                tree(&between.high)
+            ),
+            Expression::Like(like) => format!(
+                "({} {}LIKE {}{})",
+                tree(&like.expression),
+                not(like.not),
+                tree(&like.pattern),
+                match &like.escape {
+                    Some(escape) => format!(" ESCAPE {}", tree(escape)),
+                    None => String::new(),
+                }
+            ),
+            Expression::Exists(..) | Expression::Subquery(_) => "(subquery)".to_string(),
+            Expression::Quantified(quantified) => {
+                let quantifier = match quantified.quantifier {
+                    Quantifier::Any => "ANY",
+                    Quantifier::Some => "SOME",
+                    Quantifier::All => "ALL",
+                };
+                format!("{} {}", quantifier, tree(&quantified.list))
+            }
+            Expression::Parenthesized(expression) => format!("[{}]", tree(expression)),
+            Expression::ExpressionList(expressions) => format!(
+                "[{}]",
+                expressions.iter().map(tree).collect::<Vec<_>>().join(", ")
+            ),
+            Expression::FunctionCall(function) => format!(
+                "{}({}{})",
+                function
+                    .names
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>()
+                    .join("."),
+                match function.distinct {
+                    Distinc::Distinct => "DISTINCT ",
+                    Distinc::Unique => "UNIQUE ",
+                    Distinc::All => "",
+                },
+                function
+                    .arguments
+                    .iter()
+                    .map(tree)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Expression::Case(case) => {
+                let mut text = "CASE".to_string();
+                if let Some(operand) = &case.operand {
+                    text += &format!(" {}", tree(operand));
+                }
+                for when in &case.when_clauses {
+                    text += &format!(
+                        " WHEN {} THEN {}",
+                        tree(&when.condition),
+                        tree(&when.result)
+                    );
+                }
+                if let Some(else_result) = &case.else_result {
+                    text += &format!(" ELSE {}", tree(else_result));
+                }
+                text + " END"
+            }
+            Expression::Cast(cast) => format!(
+                "CAST({} AS {})",
+                tree(&cast.expression),
+                cast.data_type
+                    .parts
+                    .iter()
+                    .map(|part| match part {
+                        DataTypePart::Keyword(keyword) => format!("{:?}", keyword),
+                        DataTypePart::Name(name) => name.to_string(),
+                        DataTypePart::Value(value) => value.to_string(),
+                        DataTypePart::Dot => ".".to_string(),
+                        DataTypePart::Comma => ",".to_string(),
+                        DataTypePart::LParen => "(".to_string(),
+                        DataTypePart::RParen => ")".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            other => format!("{:?}", other),
+        }
+    }
+
+    fn not(not: bool) -> &'static str {
+        match not {
+            true => "NOT ",
+            false => "",
+        }
+    }
+
+    #[test]
+    pub fn test_arithmetic_precedence() {
+        assert_eq!("(a + (b * c))", parse_tree("a + b * c"));
+        assert_eq!("((a - b) - c)", parse_tree("a - b - c"));
+        assert_eq!("(((-a) * b) || c)", parse_tree("-a * b || c"));
+        assert_eq!("([(a + b)] / 2)", parse_tree("(a + b) / 2"));
+    }
+
+    #[test]
+    pub fn test_condition_precedence() {
+        assert_eq!(
+            "((a = 1) OR ((b = 2) AND (NOT (c = 3))))",
+            parse_tree("a = 1 or b = 2 and not c = 3")
+        );
+        assert_eq!(
+            "((a BETWEEN 1 AND (b + 1)) AND (c IS NOT NULL))",
+            parse_tree("a between 1 and b + 1 and c is not null")
+        );
+        assert_eq!(
+            "((a NOT LIKE ('x' || b) ESCAPE '!') OR (c NOT IN [1, 2]))",
+            parse_tree("a not like 'x' || b escape '!' or c not in (1, 2)")
+        );
+        assert_eq!(
+            "((a > ANY [1, 2]) AND (subquery))",
+            parse_tree("a > any (1, 2) and exists (select 1 from t)")
+        );
+        assert_eq!("(a = (subquery))", parse_tree("a = (select b from t)"));
+        assert_eq!("(some = 1)", parse_tree("some = 1"));
+    }
+
+    #[test]
+    pub fn test_functions_case_cast() {
+        assert_eq!("nvl(a, (b + 1))", parse_tree("nvl(a, b + 1)"));
+        assert_eq!(
+            "(count(*) + count(DISTINCT b))",
+            parse_tree("count(*) + count(distinct b)")
+        );
+        assert_eq!("sys.pkg.f()", parse_tree("sys.pkg.f()"));
+        assert_eq!(
+            "CASE WHEN (a = 1) THEN 'x' WHEN (b IS NULL) THEN 'y' ELSE 'z' END",
+            parse_tree("case when a = 1 then 'x' when b is null then 'y' else 'z' end")
+        );
+        assert_eq!(
+            "(CASE a WHEN 1 THEN 2 END + 1)",
+            parse_tree("case a when 1 then 2 end + 1")
+        );
+        assert_eq!(
+            "CAST(a AS Number ( 10 , 2 ))",
+            parse_tree("cast(a as number(10, 2))")
+        );
+        assert_eq!(
+            "CAST(a AS Timestamp ( 3 ) With Time Zone)",
+            parse_tree("cast(a as timestamp(3) with time zone)")
+        );
+        assert_eq!(
+            "CAST(multiset((subquery)) AS hr . address_t)",
+            parse_tree("cast(multiset(select a from t) as hr.address_t)")
+        );
+    }
+
+    #[test]
+    pub fn test_expression_errors() {
+        for query in [
+            "select nvl(a, from t",
+            "select case end from t",
+            "select case when a then b from t",
+            "select case when a b end from t",
+            "select cast(a number) from t",
+            "select cast(a as) from t",
+            "select a + from t",
+            "select a is 1 from t",
+            "select a between 1 from t",
+            "select a in 1 from t",
+            "select exists b from t",
+            "select (a, b from t",
+        ] {
+            assert!(Parser::new().parse(query).is_err(), "{}", query);
+        }
+    }
 }
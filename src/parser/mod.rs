@@ -1,13 +1,14 @@
 // use crate::AstNode;
-mod expression;
-mod statement;
+pub mod expression;
+pub mod statement;
 
 use self::statement::{parse_query_statement, DMLParseError, Statement};
 
-use crate::tokenizer::{self, keyword::Keyword, Token};
-use std::iter::Iterator;
-use std::ops::{Range, RangeInclusive, RangeTo};
-use std::slice::SliceIndex;
+use crate::tokenizer::{
+    self, keyword::Keyword, Comment, Location, Token, TokenWithSpan, TokenizeError,
+};
+use std::fmt;
+use std::ops::Range;
 
 #[derive(Debug)]
 pub struct Column {
@@ -15,8 +16,50 @@ pub struct Column {
     pub alias: Option<String>,
 }
 
+/// Comments kept with an AST node: `leading` ones stand on the lines above it, `trailing`
+/// ones follow it on its last line.
+#[derive(Debug, Default)]
+pub struct Comments {
+    pub leading: Vec<Comment>,
+    pub trailing: Vec<Comment>,
+}
+
+/// Source spellings of the keywords an AST node prints, in the order it prints them, for
+/// `Case::Preserve`. Keywords the source leaves out, like an implicit AS, have none.
+#[derive(Debug, Default)]
+pub struct Spellings(Vec<String>);
+
+impl Spellings {
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// A name as written in the source. Quoted names keep their quotes and are case sensitive, so
+/// they are always printed back untouched.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Identifier {
+    Bare(String),
+    Quoted(String),
+}
+
+impl Identifier {
+    pub fn text(&self) -> &str {
+        match self {
+            Identifier::Bare(name) | Identifier::Quoted(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text())
+    }
+}
+
 pub struct Parser {
-    tokens: Vec<Token>,
+    source: String,
+    tokens: Vec<TokenWithSpan>,
     index: usize,
     // pub ast: AstNode,
 }
@@ -24,6 +67,8 @@ pub struct Parser {
 #[derive(Debug)]
 pub enum ParserError {
     UnknownParsersError(String),
+    UnexpectedToken(Box<TokenWithSpan>),
+    Tokenize(TokenizeError),
     DMLParseError(DMLParseError),
 }
 
@@ -37,10 +82,42 @@ macro_rules! impl_from_error {
     };
 }
 impl_from_error!(DMLParseError, ParserError::DMLParseError);
+impl_from_error!(TokenizeError, ParserError::Tokenize);
+
+impl fmt::Display for ParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParserError::UnknownParsersError(message) => write!(f, "{}", message),
+            ParserError::UnexpectedToken(token) => write!(
+                f,
+                "{}: Unexpected token {:?}",
+                token.span.start, token.token
+            ),
+            ParserError::Tokenize(error) => write!(f, "{}", error),
+            ParserError::DMLParseError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ParserError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParserError::Tokenize(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Parser {
     pub fn new() -> Parser {
         Parser {
+            source: String::new(),
             tokens: vec![],
             index: 0,
         }
@@ -48,59 +125,224 @@ impl Parser {
 
     pub fn parse(&mut self, query: &str) -> Result<Vec<Statement>, ParserError> {
         let mut result: Vec<Statement> = vec![];
-        if let Ok(tokens) = tokenizer::tokenize(query) {
-            self.tokens = tokens;
-        }
+        self.tokens = tokenizer::tokenize(query)?;
+        self.source = query.to_string();
+        self.index = 0;
         // Start parsing tokens
-        let mut curr_token = self.peek_token().unwrap_or_else(|| &Token::Semicolon);
         loop {
-            match curr_token {
-                Token::EOF => break,
-                Token::Semicolon => continue,
-                Token::Keyword(Keyword::Select) => {
-                    let q = parse_query_statement(self);
-                    result.push(q);
-                }
-                _ => {
-                    // Копируем значение self.index до вызова изменяемых методов
+            let start = self.index;
+            match self.peek_token().unwrap_or(&Token::EOF) {
+                Token::EOF => {
+                    let comments = self.take_leading_comments();
+                    if !comments.is_empty() {
+                        result.push(Statement::Comments(comments));
+                    }
                     break;
                 }
+                Token::Semicolon => {
+                    self.token_next();
+                    let comments = self.take_comments_between(start..self.index);
+                    if !comments.is_empty() {
+                        result.push(Statement::Comments(comments));
+                    }
+                }
+                Token::Keyword(Keyword::Select) | Token::LParen => {
+                    let mut q = parse_query_statement(self)?;
+                    // Anything the statement parser left behind would be lost by the printer.
+                    match self.peek_token().unwrap_or(&Token::EOF) {
+                        Token::Semicolon => {
+                            self.token_next();
+                        }
+                        Token::EOF => {}
+                        _ => return Err(self.unexpected_token()),
+                    }
+                    // Comments the statement parser did not place stay with the statement.
+                    let comments = self.take_comments_between(start..self.index);
+                    if let Statement::Query(subquery) = &mut q {
+                        subquery.comments.trailing.extend(comments);
+                    }
+                    result.push(q);
+                }
+                _ => return Err(self.unexpected_token()),
             }
-
-            // После этого безопасно вызвать изменяющий метод
-            curr_token = self.token_next().unwrap_or_else(|| &Token::Semicolon);
         }
         Ok(result)
     }
 
     pub fn peek_token(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|t| &t.token)
+    }
+
+    pub fn peek_token_with_span(&self) -> Option<&TokenWithSpan> {
         self.tokens.get(self.index)
     }
 
+    /// Source text of the current token.
+    pub fn peek_text(&self) -> &str {
+        self.tokens
+            .get(self.index)
+            .map(|t| &self.source[t.span.range.clone()])
+            .unwrap_or_default()
+    }
+
+    /// The current token as a name: an identifier, a quoted identifier or a non-reserved
+    /// keyword, which keeps its spelling from the source.
+    pub fn peek_identifier(&self) -> Option<Identifier> {
+        match self.peek_token()? {
+            Token::Identifier(name) => Some(Identifier::Bare(name.to_string())),
+            Token::QuotedIdentifier(name) => Some(Identifier::Quoted(name.to_string())),
+            Token::Keyword(keyword) if !keyword.is_reserved() => {
+                Some(Identifier::Bare(self.peek_text().to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Location of the current token, or of the end of input once it is exhausted.
+    pub fn location(&self) -> Location {
+        self.tokens
+            .get(self.index)
+            .or(self.tokens.last())
+            .map(|t| t.span.start)
+            .unwrap_or(Location { line: 1, column: 1 })
+    }
+
     pub fn next_token(&mut self) -> Option<&Token> {
         //++token
         self.index += 1;
-        self.tokens.get(self.index)
+        self.tokens.get(self.index).map(|t| &t.token)
     }
 
     pub fn token_next(&mut self) -> Option<&Token> {
         //token++
-        let token = self.tokens.get(self.index);
+        let token = self.tokens.get(self.index).map(|t| &t.token);
         self.index += 1;
         token
     }
 
+    /// Consumes the current token, a keyword the AST prints back, and adds its spelling to
+    /// `spellings`.
+    pub fn keyword_next(&mut self, spellings: &mut Spellings) {
+        spellings.0.push(self.peek_text().to_string());
+        self.index += 1;
+    }
+
     pub fn prev_token(&mut self) -> Option<&Token> {
         self.index -= 1;
-        self.tokens.get(self.index)
+        self.tokens.get(self.index).map(|t| &t.token)
     }
 
     pub fn peek_nth(&self, index: usize) -> Option<&Token> {
-        self.tokens.get(self.index + index)
+        self.tokens.get(self.index + index).map(|t| &t.token)
     }
 
-    pub fn move_index(&mut self, size: usize) -> usize{
+    /// Takes the comments standing before the current token.
+    pub fn take_leading_comments(&mut self) -> Vec<Comment> {
+        self.tokens
+            .get_mut(self.index)
+            .map(|t| std::mem::take(&mut t.leading_comments))
+            .unwrap_or_default()
+    }
+
+    /// Takes the comments following the last consumed token on its line.
+    pub fn take_trailing_comments(&mut self) -> Vec<Comment> {
+        self.index
+            .checked_sub(1)
+            .and_then(|index| self.tokens.get_mut(index))
+            .map(|t| std::mem::take(&mut t.trailing_comments))
+            .unwrap_or_default()
+    }
+
+    fn take_comments_between(&mut self, range: Range<usize>) -> Vec<Comment> {
+        let mut comments = vec![];
+        for t in self.tokens[range].iter_mut() {
+            comments.append(&mut t.leading_comments);
+            comments.append(&mut t.trailing_comments);
+        }
+        comments
+    }
+
+    pub fn move_index(&mut self, size: usize) -> usize {
         self.index += size;
         self.index
     }
+
+    fn unexpected_token(&self) -> ParserError {
+        match self.peek_token_with_span() {
+            Some(token) => ParserError::UnexpectedToken(Box::new(token.clone())),
+            None => ParserError::UnknownParsersError("Unexpected end of input".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Parser, ParserError};
+    use crate::tokenizer::TokenizeError;
+
+    #[test]
+    pub fn test_parse_reports_tokenize_errors() {
+        let result = Parser::new().parse("select a from b ? c;");
+
+        match result {
+            Err(ParserError::Tokenize(TokenizeError::UnexpectedCharacter { ch, span })) => {
+                assert_eq!('?', ch);
+                assert_eq!(16..17, span.range);
+            }
+            other => panic!("Expected a tokenize error, got {:?}", other),
+        }
+        assert!(Parser::new().parse("select 'a from b;").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_empty_input() {
+        assert!(Parser::new().parse("").unwrap().is_empty());
+        assert!(Parser::new().parse(" ;\n;").unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn test_non_reserved_keywords_as_names() {
+        assert!(Parser::new().parse("select type, name x from t").is_ok());
+        assert!(Parser::new().parse("select from from t").is_err());
+        assert!(Parser::new().parse("select a from t join").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_from_errors() {
+        assert!(Parser::new().parse("select a from t,").is_err());
+        assert!(Parser::new().parse("select a from t@").is_err());
+        assert!(Parser::new().parse("select a from table(x").is_err());
+        assert!(Parser::new()
+            .parse("select a from (select b from c")
+            .is_err());
+        assert!(Parser::new().parse("select a from ()").is_err());
+        assert!(Parser::new().parse("select a from (()) x").is_err());
+        assert!(Parser::new().parse("()").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_clause_errors() {
+        assert!(Parser::new().parse("select a from t where").is_err());
+        assert!(Parser::new().parse("select a from t group a").is_err());
+        assert!(Parser::new()
+            .parse("select a from t group by rollup(a")
+            .is_err());
+        assert!(Parser::new()
+            .parse("select a from t order by a nulls")
+            .is_err());
+        assert!(Parser::new()
+            .parse("select a from t order by a desc, ")
+            .is_err());
+    }
+
+    #[test]
+    pub fn test_parser_is_reusable() {
+        let mut p = Parser::new();
+
+        assert_eq!(1, p.parse("select a from b").unwrap().len());
+        assert_eq!(
+            2,
+            p.parse("select a from b; select c from d;").unwrap().len()
+        );
+    }
 }
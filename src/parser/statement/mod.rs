@@ -1,15 +1,22 @@
-mod query;
+pub mod query;
 use crate::parser::Parser;
+use crate::tokenizer::Comment;
 pub use query::DMLParseError;
-use query::{parse_query_block, parse_subquery, SubQuery};
+use query::{parse_subquery, SubQuery};
 
 #[derive(Debug)]
 pub enum Statement {
     // SELECT
     Query(SubQuery),
+    // Comments outside of any statement, e.g. at the end of a script
+    Comments(Vec<Comment>),
 }
 
-pub fn parse_query_statement(p: &mut Parser) -> Statement {
-    let s_q = parse_subquery(p).expect("Jopa").unwrap();
-    Statement::Query(s_q)
+pub fn parse_query_statement(p: &mut Parser) -> Result<Statement, DMLParseError> {
+    match parse_subquery(p)? {
+        Some(s_q) => Ok(Statement::Query(s_q)),
+        None => Err(DMLParseError::PareseSubQueryError(
+            "Expected SELECT or WITH".to_string(),
+        )),
+    }
 }
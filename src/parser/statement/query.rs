@@ -1,132 +1,307 @@
-use super::Statement;
-use crate::parser::expression::{Expression, SimpleExpression};
-use crate::parser::{Column, Parser};
-use std::ops::{Range, RangeInclusive};
+use crate::parser::expression::{parse_commented_expression, parse_expression, Expression};
+use crate::parser::{Comments, Identifier, Parser, Spellings};
+use std::fmt;
 
-use crate::tokenizer::{self, keyword::Keyword, Token};
+use crate::tokenizer::{keyword::Keyword, Comment, Token};
 
 #[derive(Debug)]
 pub struct SubQuery {
     pub sub_query: Option<Box<SubQuery>>,
     pub query_block: Option<Box<QueryBlock>>,
+    pub comments: Comments,
 }
 
 #[derive(Debug)]
 pub struct QueryBlock {
-    with: Option<Box<With>>,
-    distinct: Distinc,
-    select_list: Vec<SelectItem>,
-    // TODO: MUST BE NOT OPTION
-    from: Option<Box<From>>,
-    // where: Option<Box<Condition>>,
+    pub with: Option<Box<With>>,
+    pub hint: Option<String>,
+    pub distinct: Distinc,
+    // Comments before and after DISTINCT, UNIQUE or ALL
+    pub distinct_comments: Comments,
+    pub select_list: Vec<SelectItem>,
+    // Comma separated, empty without a FROM clause
+    pub from: Vec<From>,
+    pub where_clause: Option<Box<Expression>>,
+    // Empty without a GROUP BY clause
+    pub group_by: Vec<GroupByItem>,
+    pub having: Option<Box<Expression>>,
+    pub order_by: Option<OrderBy>,
+    // SELECT, DISTINCT or UNIQUE and the clause keywords up to HAVING
+    pub spellings: Spellings,
+    // Comments around the SELECT keyword
+    pub comments: Comments,
 }
 
 #[derive(Debug)]
-struct With {
-    name: String,
-    aliases: Vec<SelectItem>,
-    subquery: Box<SubQuery>,
+pub struct With {
+    pub name: Identifier,
+    pub aliases: Vec<SelectItem>,
+    pub subquery: Box<SubQuery>,
 }
 
 #[derive(Debug)]
-enum Distinc {
+pub enum Distinc {
     Distinct,
     Unique,
     All,
 }
 
 #[derive(Debug)]
-enum From {
+pub enum From {
     TableReference(TableReference),
     Join(Join),
 }
 
 #[derive(Debug)]
-enum TableReference {
+pub enum TableReference {
     QueryTableExpression(QueryTableExpression),
 }
 
+impl From {
+    pub fn comments(&self) -> &Comments {
+        match self {
+            From::TableReference(table_reference) => table_reference.comments(),
+            From::Join(join) => &join.comments,
+        }
+    }
+}
+
+impl TableReference {
+    pub fn comments(&self) -> &Comments {
+        match self {
+            TableReference::QueryTableExpression(table) => &table.comments,
+        }
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        match self {
+            TableReference::QueryTableExpression(table) => &mut table.comments,
+        }
+    }
+}
+
+/// One of a table or view name, an inline view `(SELECT ...)` or a collection expression
+/// `TABLE(...)`, with an optional alias.
 #[derive(Debug)]
-struct QueryTableExpression {
-    schema: Option<String>,
-    table: Option<String>,
-    // db_link: Option<Box<DbLink>>,
-    alias: Option<String>,
+pub struct QueryTableExpression {
+    pub schema: Option<Identifier>,
+    pub table: Option<Identifier>,
+    pub db_link: Option<DbLink>,
+    pub subquery: Option<Box<SubQuery>>,
+    pub collection: Option<Box<Expression>>,
+    pub alias: Option<Identifier>,
+    pub spellings: Spellings,
+    pub comments: Comments,
 }
 
+/// `@remote.example.com`, the dot separated name of a database link.
 #[derive(Debug)]
-struct Join {
-    table_reference: TableReference,
-    join: Option<JoinClause>,
+pub struct DbLink {
+    pub names: Vec<Identifier>,
 }
 
+/// A table reference followed by the tables joined to it, `a JOIN b ON ... LEFT JOIN c ...`.
 #[derive(Debug)]
-enum JoinClause {
+pub struct Join {
+    pub table_reference: TableReference,
+    pub joins: Vec<JoinClause>,
+    // Comments around the whole join, the tables keep their own
+    pub comments: Comments,
+}
+
+#[derive(Debug)]
+pub enum JoinClause {
     InnerJoin(InnerJoin),
     OuterJoin(OuterJoin),
+    Apply(Apply),
 }
 
 #[derive(Debug)]
-struct InnerJoin {
-    inner_type: InnerJoinType,
-    join_table: TableReference,
-    // using: Vec<ColumnAccess>,
-    // condition: Box<Condition>,
+pub struct InnerJoin {
+    pub inner_type: InnerJoinType,
+    // INNER is written out
+    pub inner: bool,
+    pub join_table: TableReference,
+    pub condition: Option<JoinCondition>,
+    pub spellings: Spellings,
 }
 
 #[derive(Debug)]
-enum InnerJoinType {
+pub enum InnerJoinType {
     Inner,
     Cross,
     Natural,
 }
 
 #[derive(Debug)]
-struct OuterJoin {
-    outer_type: OuterJoinType,
-    join_table: TableReference,
-    // using: Vec<ColumnAccess>,
-    // condition: Box<Condition>,
+pub struct OuterJoin {
+    pub outer_type: OuterJoinType,
+    pub natural: bool,
+    // OUTER is written out
+    pub outer: bool,
+    // Partitioned outer join, PARTITION BY of the table on the left
+    pub partition_by: Option<QueryPartition>,
+    pub join_table: TableReference,
+    // PARTITION BY of `join_table`
+    pub join_partition_by: Option<QueryPartition>,
+    pub condition: Option<JoinCondition>,
+    pub spellings: Spellings,
+}
+
+/// `PARTITION BY a, b` of a partitioned outer join.
+#[derive(Debug)]
+pub struct QueryPartition {
+    pub expressions: Vec<Expression>,
+    pub spellings: Spellings,
 }
 
 #[derive(Debug)]
-enum OuterJoinType {
+pub enum OuterJoinType {
     Left,
     Right,
     Full,
 }
 
 #[derive(Debug)]
-pub struct Select {
-    pub distinct: bool,
-    pub select_list: Vec<SelectItem>,
+pub enum JoinCondition {
+    On(Box<Expression>, Spellings),
+    Using(Vec<Identifier>, Spellings),
 }
 
+/// `CROSS APPLY` or `OUTER APPLY`, a join to a table reference that may refer to the tables
+/// before it.
 #[derive(Debug)]
-struct SelectItem {
-    expression: Expression,
-    alias: Option<String>,
+pub struct Apply {
+    pub apply_type: ApplyType,
+    pub table: TableReference,
+    pub spellings: Spellings,
 }
+
+#[derive(Debug)]
+pub enum ApplyType {
+    Cross,
+    Outer,
+}
+
+#[derive(Debug)]
+pub struct GroupByItem {
+    pub element: GroupingElement,
+    pub comments: Comments,
+}
+
+#[derive(Debug)]
+pub enum GroupingElement {
+    Expression(Expression),
+    Rollup(Vec<Expression>, Spellings),
+    Cube(Vec<Expression>, Spellings),
+    GroupingSets(Vec<GroupingElement>, Spellings),
+    // `()`, the grand total in GROUPING SETS
+    Empty,
+}
+
+#[derive(Debug)]
+pub struct OrderBy {
+    // ORDER SIBLINGS BY of a hierarchical query
+    pub siblings: bool,
+    pub items: Vec<OrderByItem>,
+    pub spellings: Spellings,
+}
+
+#[derive(Debug)]
+pub struct OrderByItem {
+    pub expression: Expression,
+    pub direction: Option<SortDirection>,
+    pub nulls: Option<NullsPosition>,
+    pub spellings: Spellings,
+    pub comments: Comments,
+}
+
+#[derive(Debug)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug)]
+pub enum NullsPosition {
+    First,
+    Last,
+}
+
+#[derive(Debug)]
+pub struct SelectItem {
+    pub expression: Expression,
+    pub alias: Option<Identifier>,
+    // AS when it is written out
+    pub spellings: Spellings,
+    pub comments: Comments,
+}
+
 #[derive(Debug)]
 pub enum DMLParseError {
     SelectParseError(String),
     PareseSubQueryError(String),
+    FromParseError(String),
+    GroupByParseError(String),
+    OrderByParseError(String),
+    ExpressionParseError(String),
+}
+
+impl fmt::Display for DMLParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DMLParseError::SelectParseError(message) => write!(f, "SELECT: {}", message),
+            DMLParseError::PareseSubQueryError(message) => write!(f, "subquery: {}", message),
+            DMLParseError::FromParseError(message) => write!(f, "FROM: {}", message),
+            DMLParseError::GroupByParseError(message) => write!(f, "GROUP BY: {}", message),
+            DMLParseError::OrderByParseError(message) => write!(f, "ORDER BY: {}", message),
+            DMLParseError::ExpressionParseError(message) => write!(f, "expression: {}", message),
+        }
+    }
 }
 pub fn parse_subquery(p: &mut Parser) -> Result<Option<SubQuery>, DMLParseError> {
+    let leading = p.take_leading_comments();
     let token = p.peek_token();
     match token {
         Some(Token::LParen) => {
             let _ = p.token_next();
-            let subquery = SubQuery {
-                sub_query: parse_subquery(p)?.map(Box::new),
+            // `()` holds no query and would be printed as nothing.
+            let Some(sub_query) = parse_subquery(p)? else {
+                return Err(DMLParseError::PareseSubQueryError(format!(
+                    "{}: Expected SELECT or WITH, found {:?}",
+                    p.location(),
+                    p.peek_token()
+                )));
+            };
+            let mut subquery = SubQuery {
+                sub_query: Some(Box::new(sub_query)),
                 query_block: None,
+                comments: Comments::default(),
             };
-            Ok(Some(subquery))
+            match p.peek_token() {
+                Some(Token::RParen) => {
+                    let _ = p.token_next();
+                    subquery.comments = Comments {
+                        leading,
+                        trailing: p.take_trailing_comments(),
+                    };
+                    Ok(Some(subquery))
+                }
+                token => Err(DMLParseError::PareseSubQueryError(format!(
+                    "{}: Expected ')', found {:?}",
+                    p.location(),
+                    token
+                ))),
+            }
         }
         Some(Token::Keyword(Keyword::Select)) | Some(Token::Keyword(Keyword::With)) => {
             let subquery = SubQuery {
                 sub_query: None,
                 query_block: Some(Box::new(parse_query_block(p)?)),
+                comments: Comments {
+                    leading,
+                    trailing: vec![],
+                },
             };
             Ok(Some(subquery))
         }
@@ -136,339 +311,711 @@ pub fn parse_subquery(p: &mut Parser) -> Result<Option<SubQuery>, DMLParseError>
 
 pub fn parse_query_block(p: &mut Parser) -> Result<QueryBlock, DMLParseError> {
     // let with = parse_with(p)?.map(Box::new);
-    let (distinct, select_list) = parse_select_list(p)?;
-    // let from = parse_from(p)?.map(Box::new);
+    let leading = p.take_leading_comments();
+    let mut spellings = Spellings::default();
+    match p.peek_token() {
+        Some(Token::Keyword(Keyword::Select)) => p.keyword_next(&mut spellings),
+        token => {
+            return Err(DMLParseError::SelectParseError(format!(
+                "{}: Expected SELECT, found {:?}",
+                p.location(),
+                token
+            )))
+        }
+    }
+    let hint = match p.peek_token() {
+        Some(Token::Hint(hint)) => {
+            let hint = hint.to_string();
+            let _ = p.token_next();
+            Some(hint)
+        }
+        _ => None,
+    };
+    let trailing = p.take_trailing_comments();
+    let (distinct, distinct_comments) = parse_distinct(p, &mut spellings);
+    let select_list = parse_select_list(p)?;
+    let from = parse_from(p, &mut spellings)?;
+    let where_clause = parse_condition_clause(p, Keyword::Where, &mut spellings)?;
+    let group_by = parse_group_by(p, &mut spellings)?;
+    let having = parse_condition_clause(p, Keyword::Having, &mut spellings)?;
+    let order_by = parse_order_by(p)?;
     Ok(QueryBlock {
         with: None,
+        hint,
         distinct,
+        distinct_comments,
         select_list,
-        from: None,
+        from,
+        where_clause,
+        group_by,
+        having,
+        order_by,
+        spellings,
+        comments: Comments { leading, trailing },
     })
-    // Err(DMLParseError::SelectParseError("jopa".to_string()))
 }
 
-fn parse_select_list(p: &mut Parser) -> Result<(Distinc, Vec<SelectItem>), DMLParseError> {
-    let mut distinct = Distinc::All;
+/// DISTINCT, UNIQUE or ALL after SELECT, with the comments around it.
+fn parse_distinct(p: &mut Parser, spellings: &mut Spellings) -> (Distinc, Comments) {
+    let distinct = match p.peek_token() {
+        Some(Token::Keyword(Keyword::Distinct)) => Distinc::Distinct,
+        Some(Token::Keyword(Keyword::Unique)) => Distinc::Unique,
+        Some(Token::Keyword(Keyword::All)) => Distinc::All,
+        _ => return (Distinc::All, Comments::default()),
+    };
+    let leading = p.take_leading_comments();
+    match distinct {
+        // ALL is the default and is not printed.
+        Distinc::All => {
+            let _ = p.token_next();
+        }
+        _ => p.keyword_next(spellings),
+    }
+    let trailing = p.take_trailing_comments();
+    (distinct, Comments { leading, trailing })
+}
+
+fn parse_select_list(p: &mut Parser) -> Result<Vec<SelectItem>, DMLParseError> {
     let mut select_list = vec![];
-    let mut list_token = p.next_token().unwrap_or_else(|| &Token::EOF);
     loop {
-        // print!("Token<{:?}>", list_token);
-        match list_token {
-            Token::Keyword(Keyword::From) => break,
-            Token::LParen => break,
-            Token::Comma => {}
-            Token::Keyword(Keyword::Distinct) | Token::Keyword(Keyword::Unique) => {
-                distinct = Distinc::Distinct;
+        let mut item = parse_select_item(p)?;
+        if p.peek_token() != Some(&Token::Comma) {
+            select_list.push(item);
+            break;
+        }
+        let _ = p.token_next();
+        item.comments.trailing.extend(p.take_trailing_comments());
+        select_list.push(item);
+        // A trailing comma before FROM is tolerated and dropped.
+        if p.peek_token() == Some(&Token::Keyword(Keyword::From)) {
+            break;
+        }
+    }
+
+    Ok(select_list)
+}
+
+/// `WHERE condition` or `HAVING condition`.
+fn parse_condition_clause(
+    p: &mut Parser,
+    keyword: Keyword,
+    spellings: &mut Spellings,
+) -> Result<Option<Box<Expression>>, DMLParseError> {
+    if !consume_keyword(p, keyword, spellings) {
+        return Ok(None);
+    }
+    let leading = p.take_trailing_comments();
+    Ok(Some(Box::new(parse_commented_expression(p, leading)?)))
+}
+
+fn parse_group_by(
+    p: &mut Parser,
+    spellings: &mut Spellings,
+) -> Result<Vec<GroupByItem>, DMLParseError> {
+    if !consume_keyword(p, Keyword::Group, spellings) {
+        return Ok(vec![]);
+    }
+    expect_keyword(p, Keyword::By, spellings, DMLParseError::GroupByParseError)?;
+    parse_list(
+        p,
+        |p| {
+            let leading = p.take_leading_comments();
+            let element = parse_grouping_element(p)?;
+            Ok(GroupByItem {
+                element,
+                comments: Comments {
+                    leading,
+                    trailing: p.take_trailing_comments(),
+                },
+            })
+        },
+        |item| &mut item.comments,
+    )
+}
+
+fn parse_grouping_element(p: &mut Parser) -> Result<GroupingElement, DMLParseError> {
+    let mut spellings = Spellings::default();
+    let element = match (p.peek_token(), p.peek_nth(1)) {
+        (Some(Token::Keyword(Keyword::Rollup)), Some(Token::LParen)) => {
+            p.keyword_next(&mut spellings);
+            GroupingElement::Rollup(parse_parenthesized_list(p, parse_expression)?, spellings)
+        }
+        (Some(Token::Keyword(Keyword::Cube)), Some(Token::LParen)) => {
+            p.keyword_next(&mut spellings);
+            GroupingElement::Cube(parse_parenthesized_list(p, parse_expression)?, spellings)
+        }
+        (Some(Token::Keyword(Keyword::Grouping)), Some(Token::Keyword(Keyword::Sets))) => {
+            p.keyword_next(&mut spellings);
+            p.keyword_next(&mut spellings);
+            let elements = parse_parenthesized_list(p, parse_grouping_element)?;
+            GroupingElement::GroupingSets(elements, spellings)
+        }
+        (Some(Token::LParen), Some(Token::RParen)) => {
+            p.move_index(2);
+            GroupingElement::Empty
+        }
+        _ => GroupingElement::Expression(parse_expression(p)?),
+    };
+    Ok(element)
+}
+
+/// `(item, ...)` after ROLLUP, CUBE or GROUPING SETS.
+fn parse_parenthesized_list<T>(
+    p: &mut Parser,
+    parse: fn(&mut Parser) -> Result<T, DMLParseError>,
+) -> Result<Vec<T>, DMLParseError> {
+    if p.peek_token() != Some(&Token::LParen) {
+        return Err(DMLParseError::GroupByParseError(format!(
+            "{}: Expected '(', found {:?}",
+            p.location(),
+            p.peek_token()
+        )));
+    }
+    let mut items = vec![];
+    loop {
+        let _ = p.token_next();
+        items.push(parse(p)?);
+        if p.peek_token() != Some(&Token::Comma) {
+            break;
+        }
+    }
+    expect_rparen(p, DMLParseError::GroupByParseError)?;
+    Ok(items)
+}
+
+fn parse_order_by(p: &mut Parser) -> Result<Option<OrderBy>, DMLParseError> {
+    let mut spellings = Spellings::default();
+    if !consume_keyword(p, Keyword::Order, &mut spellings) {
+        return Ok(None);
+    }
+    let siblings = consume_keyword(p, Keyword::Siblings, &mut spellings);
+    expect_keyword(
+        p,
+        Keyword::By,
+        &mut spellings,
+        DMLParseError::OrderByParseError,
+    )?;
+    let items = parse_list(p, parse_order_by_item, |item| &mut item.comments)?;
+    Ok(Some(OrderBy {
+        siblings,
+        items,
+        spellings,
+    }))
+}
+
+// expression [ASC | DESC] [NULLS FIRST | NULLS LAST]
+fn parse_order_by_item(p: &mut Parser) -> Result<OrderByItem, DMLParseError> {
+    let leading = p.take_leading_comments();
+    let expression = parse_expression(p)?;
+    let mut spellings = Spellings::default();
+    let direction = match p.peek_token() {
+        Some(Token::Keyword(Keyword::Asc)) => Some(SortDirection::Asc),
+        Some(Token::Keyword(Keyword::Desc)) => Some(SortDirection::Desc),
+        _ => None,
+    };
+    if direction.is_some() {
+        p.keyword_next(&mut spellings);
+    }
+    let nulls = match consume_keyword(p, Keyword::Nulls, &mut spellings) {
+        true => {
+            let nulls = match p.peek_token() {
+                Some(Token::Keyword(Keyword::First)) => NullsPosition::First,
+                Some(Token::Keyword(Keyword::Last)) => NullsPosition::Last,
+                token => {
+                    return Err(DMLParseError::OrderByParseError(format!(
+                        "{}: Expected FIRST or LAST after NULLS, found {:?}",
+                        p.location(),
+                        token
+                    )))
+                }
+            };
+            p.keyword_next(&mut spellings);
+            Some(nulls)
+        }
+        false => None,
+    };
+    Ok(OrderByItem {
+        expression,
+        direction,
+        nulls,
+        spellings,
+        comments: Comments {
+            leading,
+            trailing: p.take_trailing_comments(),
+        },
+    })
+}
+
+/// Items separated by commas. Comments right after a comma go with the item before it.
+fn parse_list<T>(
+    p: &mut Parser,
+    parse: fn(&mut Parser) -> Result<T, DMLParseError>,
+    comments: fn(&mut T) -> &mut Comments,
+) -> Result<Vec<T>, DMLParseError> {
+    let mut items = vec![];
+    loop {
+        let mut item = parse(p)?;
+        let comma = p.peek_token() == Some(&Token::Comma);
+        if comma {
+            let _ = p.token_next();
+            comments(&mut item)
+                .trailing
+                .extend(p.take_trailing_comments());
+        }
+        items.push(item);
+        if !comma {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+// trying to parse select list.
+// See the schema: https://docs.oracle.com/en/database/oracle/oracle-database/21/sqlrf/SELECT.html#GUID-CFA006CA-6FF1-4972-821E-6996142A51C6
+// Variants:
+// 1. schema.table.*
+// 2. table.*
+// 3. *
+// 4. expression [[as] alias]
+fn parse_select_item(p: &mut Parser) -> Result<SelectItem, DMLParseError> {
+    let leading = p.take_leading_comments();
+    let expression = parse_expression(p)?;
+    let mut spellings = Spellings::default();
+    let alias = match &expression {
+        Expression::SimpleExpression(simple) if simple.column.text() == "*" => None,
+        _ => parse_alias(p, &mut spellings)?,
+    };
+    let trailing = p.take_trailing_comments();
+
+    Ok(SelectItem {
+        expression,
+        alias,
+        spellings,
+        comments: Comments { leading, trailing },
+    })
+}
+
+fn parse_alias(
+    p: &mut Parser,
+    spellings: &mut Spellings,
+) -> Result<Option<Identifier>, DMLParseError> {
+    if p.peek_token() != Some(&Token::Keyword(Keyword::As)) {
+        return Ok(parse_implicit_alias(p));
+    }
+    p.keyword_next(spellings);
+    match parse_identifier(p) {
+        Some(alias) => Ok(Some(alias)),
+        None => Err(DMLParseError::SelectParseError(format!(
+            "{}: Expected alias after AS, found {:?}",
+            p.location(),
+            p.peek_token()
+        ))),
+    }
+}
+
+// Non-reserved words that Oracle reads as the start of what follows rather than as an alias
+// written without AS.
+const NOT_AN_ALIAS: [Keyword; 14] = [
+    Keyword::Bulk,
+    Keyword::Cross,
+    Keyword::Fetch,
+    Keyword::Full,
+    Keyword::Inner,
+    Keyword::Join,
+    Keyword::Left,
+    Keyword::Model,
+    Keyword::Natural,
+    Keyword::Offset,
+    Keyword::Outer,
+    Keyword::Partition,
+    Keyword::Right,
+    Keyword::Using,
+];
+
+fn parse_implicit_alias(p: &mut Parser) -> Option<Identifier> {
+    match p.peek_token() {
+        Some(Token::Keyword(keyword)) if NOT_AN_ALIAS.contains(keyword) => None,
+        _ => parse_identifier(p),
+    }
+}
+
+/// Consumes the current token if it can be used as a name.
+pub(crate) fn parse_identifier(p: &mut Parser) -> Option<Identifier> {
+    let identifier = p.peek_identifier()?;
+    let _ = p.token_next();
+    Some(identifier)
+}
+
+fn parse_from(p: &mut Parser, spellings: &mut Spellings) -> Result<Vec<From>, DMLParseError> {
+    if p.peek_token() != Some(&Token::Keyword(Keyword::From)) {
+        return Ok(vec![]);
+    }
+    // Comments around the FROM keyword go above the first table reference.
+    let mut leading = p.take_leading_comments();
+    p.keyword_next(spellings);
+    leading.extend(p.take_trailing_comments());
+
+    let mut from = vec![];
+    loop {
+        let mut table_reference = parse_table_reference(p)?;
+        let joins = parse_joins(p)?;
+        let mut item = match joins.is_empty() {
+            true => From::TableReference(table_reference),
+            false => From::Join(Join {
+                comments: Comments {
+                    leading: std::mem::take(&mut table_reference.comments_mut().leading),
+                    trailing: vec![],
+                },
+                table_reference,
+                joins,
+            }),
+        };
+        let comments = match &mut item {
+            From::TableReference(table_reference) => table_reference.comments_mut(),
+            From::Join(join) => &mut join.comments,
+        };
+        if from.is_empty() {
+            leading.append(&mut comments.leading);
+            comments.leading = std::mem::take(&mut leading);
+        }
+        let comma = p.peek_token() == Some(&Token::Comma);
+        if comma {
+            let _ = p.token_next();
+            comments.trailing.extend(p.take_trailing_comments());
+        }
+        from.push(item);
+        if !comma {
+            break;
+        }
+    }
+    Ok(from)
+}
+
+fn parse_joins(p: &mut Parser) -> Result<Vec<JoinClause>, DMLParseError> {
+    let mut joins = vec![];
+    while starts_join(p) {
+        // Comments before the join keywords go above the joined table.
+        let mut leading = p.take_leading_comments();
+        let partition_by = parse_query_partition(p)?;
+        let mut spellings = Spellings::default();
+        let natural = consume_keyword(p, Keyword::Natural, &mut spellings);
+        let clause = match p.peek_token() {
+            Some(Token::Keyword(Keyword::Cross | Keyword::Outer))
+                if p.peek_nth(1) == Some(&Token::Keyword(Keyword::Apply))
+                    && !natural
+                    && partition_by.is_none() =>
+            {
+                let apply_type = match p.peek_token() {
+                    Some(Token::Keyword(Keyword::Cross)) => ApplyType::Cross,
+                    _ => ApplyType::Outer,
+                };
+                p.keyword_next(&mut spellings);
+                p.keyword_next(&mut spellings);
+                leading.extend(p.take_trailing_comments());
+                let mut table = parse_table_reference(p)?;
+                prepend_comments(&mut table, leading);
+                JoinClause::Apply(Apply {
+                    apply_type,
+                    table,
+                    spellings,
+                })
             }
-            Token::Asterisk(_) => {
-                let expression = SimpleExpression {
-                    table: None,
-                    schema: None,
-                    column: "*".to_string(),
+            Some(Token::Keyword(Keyword::Left | Keyword::Right | Keyword::Full)) => {
+                let outer_type = match p.peek_token() {
+                    Some(Token::Keyword(Keyword::Left)) => OuterJoinType::Left,
+                    Some(Token::Keyword(Keyword::Right)) => OuterJoinType::Right,
+                    _ => OuterJoinType::Full,
                 };
-
-                select_list.push(SelectItem {
-                    expression: Expression::SimpleExpression(expression),
-                    alias: None,
+                p.keyword_next(&mut spellings);
+                let outer = consume_keyword(p, Keyword::Outer, &mut spellings);
+                expect_keyword(
+                    p,
+                    Keyword::Join,
+                    &mut spellings,
+                    DMLParseError::FromParseError,
+                )?;
+                leading.extend(p.take_trailing_comments());
+                let mut join_table = parse_table_reference(p)?;
+                prepend_comments(&mut join_table, leading);
+                let join_partition_by = parse_query_partition(p)?;
+                JoinClause::OuterJoin(OuterJoin {
+                    outer_type,
+                    natural,
+                    outer,
+                    partition_by,
+                    join_table,
+                    join_partition_by,
+                    condition: parse_join_condition(p)?,
+                    spellings,
                 })
             }
-            // trying to parse select list.
-            // See the schema: https://docs.oracle.com/en/database/oracle/oracle-database/21/sqlrf/SELECT.html#GUID-CFA006CA-6FF1-4972-821E-6996142A51C6
-            // Variants:
-            // 1. schema.table.column as alias
-            // 2. schema.table.column alias
-            // 3. schema.table.column
-            // 4. schema.table.*
-
-            // 5. table.column as alias
-            // 6. table.column alias
-            // 7. table.column
-            // 8. table.*
-
-            // 9. column as alias
-            // 10. column alias
-            // 11. column
-
-            // TODO: refactor this s..
-            Token::Identifier(_) => {
-                let (t1, t2, t3, t4, t5, t6, t7, t8): (
-                    Option<&Token>,
-                    Option<&Token>,
-                    Option<&Token>,
-                    Option<&Token>,
-                    Option<&Token>,
-                    Option<&Token>,
-                    Option<&Token>,
-                    Option<&Token>,
-                ) = (
-                    p.peek_nth(0),
-                    p.peek_nth(1),
-                    p.peek_nth(2),
-                    p.peek_nth(3),
-                    p.peek_nth(4),
-                    p.peek_nth(5),
-                    p.peek_nth(6),
-                    p.peek_nth(7),
-                );
-                match (t1, t2, t3, t4, t5, t6, t7, t8) {
-                    // 1. schema.table.column as alias
-                    (
-                        Some(Token::Identifier(schema)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(column)),
-                        Some(Token::Keyword(Keyword::As)),
-                        Some(Token::Identifier(alias)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: Some(schema.to_string()),
-                            table: Some(table.to_string()),
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: Some(alias.to_string()),
-                        });
-                        println!("1: {:?} {:?}", t1, t2);
-                        let idx = p.move_index(7);
-                        println!("1: {:?}", idx);
-                    }
-                    // 2. schema.table.column alias
-                    (
-                        Some(Token::Identifier(schema)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(column)),
-                        Some(Token::Identifier(alias)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: Some(schema.to_string()),
-                            table: Some(table.to_string()),
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: Some(alias.to_string()),
-                        });
-                        let _ = p.move_index(6);
-                    }
-                    // 3. schema.table.column
-                    (
-                        Some(Token::Identifier(schema)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(column)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: Some(schema.to_string()),
-                            table: Some(table.to_string()),
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: None,
-                        });
-                        let _ = p.move_index(5);
-                    }
-                    // 4. schema.table.*
-                    (
-                        Some(Token::Identifier(schema)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Asterisk('*')),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: Some(schema.to_string()),
-                            table: Some(table.to_string()),
-                            column: "*".to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: None,
-                        });
-                        let _ = p.move_index(4);
-                    }
-                    // 5. table.column as alias
-                    (
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(column)),
-                        Some(Token::Keyword(Keyword::As)),
-                        Some(Token::Identifier(alias)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: Some(table.to_string()),
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: Some(alias.to_string()),
-                        });
-                        let _ = p.move_index(4);
-                    }
-                    // 6. table.column alias
-                    (
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(column)),
-                        Some(Token::Identifier(alias)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: Some(table.to_string()),
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: None,
-                        });
-                        let _ = p.move_index(3);
-                    }
-                    // 7. table.column
-                    (
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Identifier(column)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: Some(table.to_string()),
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: None,
-                        });
-                        let _ = p.move_index(2);
-                    }
-                    // 8. table.*
-                    (
-                        Some(Token::Identifier(table)),
-                        Some(Token::Dot),
-                        Some(Token::Asterisk('*')),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: Some(table.to_string()),
-                            column: "*".to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: None,
-                        });
-                        let _ = p.move_index(2);
-                    }
-                    // 9. column as alias
-                    (
-                        Some(Token::Identifier(column)),
-                        Some(Token::Keyword(Keyword::As)),
-                        Some(Token::Identifier(alias)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: None,
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: Some(alias.to_string()),
-                        });
-                        let _ = p.move_index(3);
-                    }
-                    // 10. column alias
-                    (
-                        Some(Token::Identifier(column)),
-                        Some(Token::Identifier(alias)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                        _,
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: None,
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: Some(alias.to_string()),
-                        });
-                        let _ = p.move_index(2);
-                    }
-                    // 11. column
-                    (
-                        Some(Token::Identifier(column)),
-                        Some(Token::Comma) | Some(Token::Keyword(Keyword::From)),
-                        _,
-                        _,
-                        _,
-                        _,
-                        _,
-                        _,
-                    ) => {
-                        let expression = SimpleExpression {
-                            schema: None,
-                            table: None,
-                            column: column.to_string(),
-                        };
-                        select_list.push(SelectItem {
-                            expression: Expression::SimpleExpression(expression),
-                            alias: None,
-                        });
-                        let _ = p.move_index(1);
+            _ if partition_by.is_some() => {
+                return Err(DMLParseError::FromParseError(format!(
+                    "{}: Expected an outer join after PARTITION BY, found {:?}",
+                    p.location(),
+                    p.peek_token()
+                )))
+            }
+            _ => {
+                let inner_type = match natural {
+                    true => InnerJoinType::Natural,
+                    false if consume_keyword(p, Keyword::Cross, &mut spellings) => {
+                        InnerJoinType::Cross
                     }
-                    _ => {}
-                }
+                    false => InnerJoinType::Inner,
+                };
+                let inner = !matches!(inner_type, InnerJoinType::Cross)
+                    && consume_keyword(p, Keyword::Inner, &mut spellings);
+                expect_keyword(
+                    p,
+                    Keyword::Join,
+                    &mut spellings,
+                    DMLParseError::FromParseError,
+                )?;
+                leading.extend(p.take_trailing_comments());
+                let mut join_table = parse_table_reference(p)?;
+                prepend_comments(&mut join_table, leading);
+                let condition = match inner_type {
+                    InnerJoinType::Inner => parse_join_condition(p)?,
+                    InnerJoinType::Cross | InnerJoinType::Natural => None,
+                };
+                JoinClause::InnerJoin(InnerJoin {
+                    inner_type,
+                    inner,
+                    join_table,
+                    condition,
+                    spellings,
+                })
+            }
+        };
+        joins.push(clause);
+    }
+    Ok(joins)
+}
+
+fn starts_join(p: &Parser) -> bool {
+    match p.peek_token() {
+        Some(Token::Keyword(
+            Keyword::Join
+            | Keyword::Inner
+            | Keyword::Left
+            | Keyword::Right
+            | Keyword::Full
+            | Keyword::Cross
+            | Keyword::Natural,
+        )) => true,
+        Some(Token::Keyword(Keyword::Outer)) => {
+            p.peek_nth(1) == Some(&Token::Keyword(Keyword::Apply))
+        }
+        Some(Token::Keyword(Keyword::Partition)) => {
+            p.peek_nth(1) == Some(&Token::Keyword(Keyword::By))
+        }
+        _ => false,
+    }
+}
+
+fn prepend_comments(table_reference: &mut TableReference, mut comments: Vec<Comment>) {
+    let table_comments = table_reference.comments_mut();
+    comments.append(&mut table_comments.leading);
+    table_comments.leading = comments;
+}
+
+/// `PARTITION BY a, b` or `PARTITION BY (a, b)` of a partitioned outer join.
+fn parse_query_partition(p: &mut Parser) -> Result<Option<QueryPartition>, DMLParseError> {
+    let mut spellings = Spellings::default();
+    if !consume_keyword(p, Keyword::Partition, &mut spellings) {
+        return Ok(None);
+    }
+    expect_keyword(
+        p,
+        Keyword::By,
+        &mut spellings,
+        DMLParseError::FromParseError,
+    )?;
+    let parenthesized = p.peek_token() == Some(&Token::LParen);
+    if parenthesized {
+        let _ = p.token_next();
+    }
+    let mut expressions = vec![parse_expression(p)?];
+    while p.peek_token() == Some(&Token::Comma) {
+        let _ = p.token_next();
+        expressions.push(parse_expression(p)?);
+    }
+    if parenthesized {
+        expect_rparen(p, DMLParseError::FromParseError)?;
+    }
+    Ok(Some(QueryPartition {
+        expressions,
+        spellings,
+    }))
+}
+
+fn parse_join_condition(p: &mut Parser) -> Result<Option<JoinCondition>, DMLParseError> {
+    let mut spellings = Spellings::default();
+    if consume_keyword(p, Keyword::On, &mut spellings) {
+        let leading = p.take_trailing_comments();
+        let condition = parse_commented_expression(p, leading)?;
+        return Ok(Some(JoinCondition::On(Box::new(condition), spellings)));
+    }
+    if !consume_keyword(p, Keyword::Using, &mut spellings) {
+        return Ok(None);
+    }
+    if p.peek_token() != Some(&Token::LParen) {
+        return Err(DMLParseError::FromParseError(format!(
+            "{}: Expected '(' after USING, found {:?}",
+            p.location(),
+            p.peek_token()
+        )));
+    }
+    let mut columns = vec![];
+    loop {
+        let _ = p.token_next();
+        match parse_identifier(p) {
+            Some(column) => columns.push(column),
+            None => {
+                return Err(DMLParseError::FromParseError(format!(
+                    "{}: Expected column name, found {:?}",
+                    p.location(),
+                    p.peek_token()
+                )))
             }
-            _ => break,
         }
-        list_token = p.next_token().unwrap_or_else(|| &Token::EOF);
-        println!("{:?}", list_token);
+        if p.peek_token() != Some(&Token::Comma) {
+            break;
+        }
     }
+    expect_rparen(p, DMLParseError::FromParseError)?;
+    Ok(Some(JoinCondition::Using(columns, spellings)))
+}
 
-    Ok((distinct, select_list))
+fn parse_table_reference(p: &mut Parser) -> Result<TableReference, DMLParseError> {
+    // Variants:
+    // 1. [schema.]table[@dblink] [alias]
+    // 2. (subquery) [alias]
+    // 3. TABLE(collection_expression) [alias]
+    let leading = p.take_leading_comments();
+    let mut table = QueryTableExpression {
+        schema: None,
+        table: None,
+        db_link: None,
+        subquery: None,
+        collection: None,
+        alias: None,
+        spellings: Spellings::default(),
+        comments: Comments::default(),
+    };
+    match p.peek_token() {
+        Some(Token::LParen) => table.subquery = parse_subquery(p)?.map(Box::new),
+        Some(Token::Keyword(Keyword::Table)) if p.peek_nth(1) == Some(&Token::LParen) => {
+            p.keyword_next(&mut table.spellings);
+            let _ = p.token_next();
+            table.collection = Some(Box::new(parse_expression(p)?));
+            expect_rparen(p, DMLParseError::FromParseError)?;
+        }
+        _ => {
+            let (schema, name) = parse_table_name(p)?;
+            table.schema = schema;
+            table.table = Some(name);
+            if p.peek_token() == Some(&Token::AtSign) {
+                let _ = p.token_next();
+                table.db_link = Some(parse_db_link(p)?);
+            }
+        }
+    }
+    table.alias = parse_implicit_alias(p);
+    table.comments = Comments {
+        leading,
+        trailing: p.take_trailing_comments(),
+    };
+    Ok(TableReference::QueryTableExpression(table))
+}
+
+fn parse_table_name(p: &mut Parser) -> Result<(Option<Identifier>, Identifier), DMLParseError> {
+    let Some(name) = parse_identifier(p) else {
+        return Err(DMLParseError::FromParseError(format!(
+            "{}: Expected table name, found {:?}",
+            p.location(),
+            p.peek_token()
+        )));
+    };
+    if p.peek_token() != Some(&Token::Dot) {
+        return Ok((None, name));
+    }
+    let _ = p.token_next();
+    match parse_identifier(p) {
+        Some(table) => Ok((Some(name), table)),
+        None => Err(DMLParseError::FromParseError(format!(
+            "{}: Expected table name after '.', found {:?}",
+            p.location(),
+            p.peek_token()
+        ))),
+    }
+}
+
+fn parse_db_link(p: &mut Parser) -> Result<DbLink, DMLParseError> {
+    let mut names = vec![];
+    loop {
+        match parse_identifier(p) {
+            Some(name) => names.push(name),
+            None => {
+                return Err(DMLParseError::FromParseError(format!(
+                    "{}: Expected database link name, found {:?}",
+                    p.location(),
+                    p.peek_token()
+                )))
+            }
+        }
+        if p.peek_token() != Some(&Token::Dot) {
+            break;
+        }
+        let _ = p.token_next();
+    }
+    Ok(DbLink { names })
+}
+
+/// Consumes `keyword` if it is next, adding its spelling to `spellings`.
+pub(crate) fn consume_keyword(p: &mut Parser, keyword: Keyword, spellings: &mut Spellings) -> bool {
+    let found = p.peek_token() == Some(&Token::Keyword(keyword));
+    if found {
+        p.keyword_next(spellings);
+    }
+    found
+}
+
+/// Consumes `keyword`, reporting anything else with `error`, like `DMLParseError::FromParseError`.
+pub(crate) fn expect_keyword(
+    p: &mut Parser,
+    keyword: Keyword,
+    spellings: &mut Spellings,
+    error: fn(String) -> DMLParseError,
+) -> Result<(), DMLParseError> {
+    match p.peek_token() {
+        Some(Token::Keyword(found)) if *found == keyword => {
+            p.keyword_next(spellings);
+            Ok(())
+        }
+        token => Err(error(format!(
+            "{}: Expected {}, found {:?}",
+            p.location(),
+            keyword,
+            token
+        ))),
+    }
 }
 
-// fn parse_select_item(p: &mut Parser) -> Result<ColumnAccess, DMLParseError> {
-//     Err(DMLParseError::SelectParseError(
-//         "Not implemented".to_string(),
-//     ))
-// }
+pub(crate) fn expect_rparen(
+    p: &mut Parser,
+    error: fn(String) -> DMLParseError,
+) -> Result<(), DMLParseError> {
+    match p.peek_token() {
+        Some(Token::RParen) => {
+            let _ = p.token_next();
+            Ok(())
+        }
+        token => Err(error(format!(
+            "{}: Expected ')', found {:?}",
+            p.location(),
+            token
+        ))),
+    }
+}
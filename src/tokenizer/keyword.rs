@@ -1,45 +1,297 @@
+use std::fmt;
 use std::str::FromStr;
-use derive_more::Display;
-
-#[derive(Debug, Display, PartialEq, Clone)]
-pub enum Keyword {
-    With,
-    Select,
-    From,
-    As,
-    Left,
-    Right,
-    Join,
-    On,
-    And,
-    Or,
-    Union,
-    All,
-    Distinct,
-    Unique,
+
+macro_rules! keywords {
+    (
+        reserved { $($reserved:ident => $reserved_text:literal,)* }
+        non_reserved { $($non_reserved:ident => $non_reserved_text:literal,)* }
+    ) => {
+        #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+        pub enum Keyword {
+            $($reserved,)*
+            $($non_reserved,)*
+        }
+
+        impl Keyword {
+            /// The keyword as Oracle spells it.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Keyword::$reserved => $reserved_text,)*
+                    $(Keyword::$non_reserved => $non_reserved_text,)*
+                }
+            }
+
+            /// Reserved words can never name anything; non-reserved ones are keywords only
+            /// where the grammar expects them and may be used as names everywhere else.
+            pub fn is_reserved(&self) -> bool {
+                matches!(self, $(Keyword::$reserved)|*)
+            }
+        }
+
+        impl FromStr for Keyword {
+            type Err = ParseKeywordError;
+            fn from_str(s: &str) -> Result<Keyword, ParseKeywordError> {
+                match s.trim().to_uppercase().as_str() {
+                    $($reserved_text => Ok(Keyword::$reserved),)*
+                    $($non_reserved_text => Ok(Keyword::$non_reserved),)*
+                    _ => Err(ParseKeywordError),
+                }
+            }
+        }
+    };
+}
+
+// See https://docs.oracle.com/en/database/oracle/oracle-database/21/sqlrf/Oracle-SQL-Reserved-Words.html
+keywords! {
+    reserved {
+        Access => "ACCESS",
+        Add => "ADD",
+        All => "ALL",
+        Alter => "ALTER",
+        And => "AND",
+        Any => "ANY",
+        As => "AS",
+        Asc => "ASC",
+        Audit => "AUDIT",
+        Between => "BETWEEN",
+        By => "BY",
+        Char => "CHAR",
+        Check => "CHECK",
+        Cluster => "CLUSTER",
+        Column => "COLUMN",
+        Comment => "COMMENT",
+        Compress => "COMPRESS",
+        Connect => "CONNECT",
+        Create => "CREATE",
+        Current => "CURRENT",
+        Date => "DATE",
+        Decimal => "DECIMAL",
+        Default => "DEFAULT",
+        Delete => "DELETE",
+        Desc => "DESC",
+        Distinct => "DISTINCT",
+        Drop => "DROP",
+        Else => "ELSE",
+        Exclusive => "EXCLUSIVE",
+        Exists => "EXISTS",
+        File => "FILE",
+        Float => "FLOAT",
+        For => "FOR",
+        From => "FROM",
+        Grant => "GRANT",
+        Group => "GROUP",
+        Having => "HAVING",
+        Identified => "IDENTIFIED",
+        Immediate => "IMMEDIATE",
+        In => "IN",
+        Increment => "INCREMENT",
+        Index => "INDEX",
+        Initial => "INITIAL",
+        Insert => "INSERT",
+        Integer => "INTEGER",
+        Intersect => "INTERSECT",
+        Into => "INTO",
+        Is => "IS",
+        Level => "LEVEL",
+        Like => "LIKE",
+        Lock => "LOCK",
+        Long => "LONG",
+        Maxextents => "MAXEXTENTS",
+        Minus => "MINUS",
+        Mlslabel => "MLSLABEL",
+        Mode => "MODE",
+        Modify => "MODIFY",
+        Noaudit => "NOAUDIT",
+        Nocompress => "NOCOMPRESS",
+        Not => "NOT",
+        Nowait => "NOWAIT",
+        Null => "NULL",
+        Number => "NUMBER",
+        Of => "OF",
+        Offline => "OFFLINE",
+        On => "ON",
+        Online => "ONLINE",
+        Option => "OPTION",
+        Or => "OR",
+        Order => "ORDER",
+        Pctfree => "PCTFREE",
+        Prior => "PRIOR",
+        Public => "PUBLIC",
+        Raw => "RAW",
+        Rename => "RENAME",
+        Resource => "RESOURCE",
+        Revoke => "REVOKE",
+        Row => "ROW",
+        Rowid => "ROWID",
+        Rownum => "ROWNUM",
+        Rows => "ROWS",
+        Select => "SELECT",
+        Session => "SESSION",
+        Set => "SET",
+        Share => "SHARE",
+        Size => "SIZE",
+        Smallint => "SMALLINT",
+        Start => "START",
+        Successful => "SUCCESSFUL",
+        Synonym => "SYNONYM",
+        Sysdate => "SYSDATE",
+        Table => "TABLE",
+        Then => "THEN",
+        To => "TO",
+        Trigger => "TRIGGER",
+        Uid => "UID",
+        Union => "UNION",
+        Unique => "UNIQUE",
+        Update => "UPDATE",
+        User => "USER",
+        Validate => "VALIDATE",
+        Values => "VALUES",
+        Varchar => "VARCHAR",
+        Varchar2 => "VARCHAR2",
+        View => "VIEW",
+        Whenever => "WHENEVER",
+        Where => "WHERE",
+        With => "WITH",
+    }
+    non_reserved {
+        Apply => "APPLY",
+        At => "AT",
+        Begin => "BEGIN",
+        BinaryDouble => "BINARY_DOUBLE",
+        BinaryFloat => "BINARY_FLOAT",
+        Breadth => "BREADTH",
+        Bulk => "BULK",
+        Case => "CASE",
+        Cast => "CAST",
+        Collect => "COLLECT",
+        Constraint => "CONSTRAINT",
+        Cross => "CROSS",
+        Cube => "CUBE",
+        Cycle => "CYCLE",
+        Day => "DAY",
+        Declare => "DECLARE",
+        Depth => "DEPTH",
+        End => "END",
+        Escape => "ESCAPE",
+        Exception => "EXCEPTION",
+        Fetch => "FETCH",
+        First => "FIRST",
+        Following => "FOLLOWING",
+        Full => "FULL",
+        Function => "FUNCTION",
+        Grouping => "GROUPING",
+        Hour => "HOUR",
+        If => "IF",
+        Ignore => "IGNORE",
+        Inner => "INNER",
+        Interval => "INTERVAL",
+        Join => "JOIN",
+        Keep => "KEEP",
+        Key => "KEY",
+        Last => "LAST",
+        Lateral => "LATERAL",
+        Left => "LEFT",
+        Local => "LOCAL",
+        Locked => "LOCKED",
+        Loop => "LOOP",
+        Matched => "MATCHED",
+        Member => "MEMBER",
+        Merge => "MERGE",
+        Minute => "MINUTE",
+        Model => "MODEL",
+        Month => "MONTH",
+        Multiset => "MULTISET",
+        Name => "NAME",
+        Natural => "NATURAL",
+        Next => "NEXT",
+        Nocycle => "NOCYCLE",
+        Nulls => "NULLS",
+        Offset => "OFFSET",
+        Only => "ONLY",
+        Outer => "OUTER",
+        Over => "OVER",
+        Package => "PACKAGE",
+        Partition => "PARTITION",
+        Percent => "PERCENT",
+        Pivot => "PIVOT",
+        Preceding => "PRECEDING",
+        Primary => "PRIMARY",
+        Procedure => "PROCEDURE",
+        Range => "RANGE",
+        References => "REFERENCES",
+        Replace => "REPLACE",
+        Respect => "RESPECT",
+        Return => "RETURN",
+        Returning => "RETURNING",
+        Right => "RIGHT",
+        Rollup => "ROLLUP",
+        Sample => "SAMPLE",
+        Search => "SEARCH",
+        Second => "SECOND",
+        Sets => "SETS",
+        Siblings => "SIBLINGS",
+        Skip => "SKIP",
+        Some => "SOME",
+        Ties => "TIES",
+        Time => "TIME",
+        Timestamp => "TIMESTAMP",
+        Truncate => "TRUNCATE",
+        Type => "TYPE",
+        Unbounded => "UNBOUNDED",
+        Unpivot => "UNPIVOT",
+        Using => "USING",
+        Wait => "WAIT",
+        When => "WHEN",
+        Within => "WITHIN",
+        Year => "YEAR",
+        Zone => "ZONE",
+    }
+}
+
+impl Keyword {
+    /// Reserved words that Oracle still reads as values, `SELECT rownum, sysdate FROM dual`.
+    pub fn is_pseudocolumn(&self) -> bool {
+        matches!(
+            self,
+            Keyword::Level
+                | Keyword::Rowid
+                | Keyword::Rownum
+                | Keyword::Sysdate
+                | Keyword::Uid
+                | Keyword::User
+        )
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 pub struct ParseKeywordError;
 
-impl FromStr for Keyword {
-    type Err = ParseKeywordError;
-    fn from_str(s: &str) -> Result<Keyword, ParseKeywordError> {
-        match s.trim().to_lowercase().as_str() {
-            "with" => Ok(Keyword::With),
-            "select" => Ok(Keyword::Select),
-            "from" => Ok(Keyword::From),
-            "as" => Ok(Keyword::As),
-            "left" => Ok(Keyword::Left),
-            "right" => Ok(Keyword::Right),
-            "join" => Ok(Keyword::Join),
-            "on" => Ok(Keyword::On),
-            "and" => Ok(Keyword::And),
-            "or" => Ok(Keyword::Or),
-            "union" => Ok(Keyword::Union),
-            "all" => Ok(Keyword::All),
-            "distinct" => Ok(Keyword::Distinct),
-            "unique" => Ok(Keyword::Unique),
-            _ => Err(ParseKeywordError),
-        }
+#[cfg(test)]
+mod test {
+    use super::Keyword;
+
+    #[test]
+    pub fn test_keyword_lookup() {
+        assert!(matches!("sElEcT".parse::<Keyword>(), Ok(Keyword::Select)));
+        assert!(matches!(
+            "binary_double".parse::<Keyword>(),
+            Ok(Keyword::BinaryDouble)
+        ));
+        assert!("selects".parse::<Keyword>().is_err());
+        assert_eq!("VARCHAR2", Keyword::Varchar2.as_str());
+        assert_eq!("BINARY_FLOAT", Keyword::BinaryFloat.to_string());
+    }
+
+    #[test]
+    pub fn test_reserved_words() {
+        assert!(Keyword::Select.is_reserved());
+        assert!(Keyword::Rownum.is_reserved());
+        assert!(!Keyword::Type.is_reserved());
+        assert!(!Keyword::Join.is_reserved());
     }
 }
@@ -2,6 +2,8 @@ pub mod keyword;
 mod string_buf;
 
 use derive_more::Display;
+use std::fmt;
+use std::ops::Range;
 
 use self::keyword::Keyword;
 use self::string_buf::{StringBuf, StringBufIterator};
@@ -11,15 +13,54 @@ pub enum Token {
     Keyword(Keyword),
 
     Identifier(String),
+    // "Name" as written, quotes included, case sensitive
+    QuotedIdentifier(String),
 
+    // Lexeme as written, `007`, `1.50` and `2e-3f` are kept as they are
     NumberLiteral(String),
+    // Source text with quotes and any `N`/`Q` prefix, so it is printed back unchanged
     StringLiteral(String),
 
+    // Wildcard or multiplication, the parser tells them apart by position
     Asterisk(char),
+    DoubleAsterisk,
+
+    Plus,
+    Minus,
+    Slash,
+    Percent,
+    // ||
+    Concat,
 
     Eq,
+    // <>
+    Neq,
+    // !=
+    BangEq,
+    // ^=
+    CaretEq,
+    // ~=
+    TildeEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+
+    // `:name`, `:1` or `:new.col`, as written
+    BindVariable(String),
+    // SQL*Plus `&name` or `&&name`, as written
+    SubstitutionVariable(String),
+
+    // :=
+    Assignment,
+    // =>
+    Arrow,
+    // @, database links
+    AtSign,
 
     Dot,
+    // .., PL/SQL ranges
+    DoubleDot,
     Comma,
 
     Colon,
@@ -28,93 +69,591 @@ pub enum Token {
     LParen,
     RParen,
 
+    // Optimizer hint, `/*+ ... */` or `--+ ...`, kept with its delimiters
+    Hint(String),
+
     EOF,
 }
 
-pub fn tokenize(query: &str) -> Result<Vec<Token>, String> {
-    let mut tokens = vec![];
+/// A position in the source text. Both fields start at 1, columns count characters.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    /// Byte offsets into the source text.
+    pub range: Range<usize>,
+    pub start: Location,
+    pub end: Location,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CommentKind {
+    /// `-- ...` up to the end of the line.
+    Line,
+    /// `/* ... */`, possibly spanning several lines.
+    Block,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Comment {
+    pub kind: CommentKind,
+    /// Source text including the comment delimiters.
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TokenWithSpan {
+    pub token: Token,
+    pub span: Span,
+    /// Comments on the lines between the previous token and this one.
+    pub leading_comments: Vec<Comment>,
+    /// Comments that start on the line this token ends on.
+    pub trailing_comments: Vec<Comment>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenizeError {
+    UnexpectedCharacter { ch: char, span: Span },
+    UnterminatedString { span: Span },
+    UnterminatedQuotedIdentifier { span: Span },
+    UnterminatedComment { span: Span },
+    MalformedNumber { literal: String, span: Span },
+}
+
+impl TokenizeError {
+    pub fn span(&self) -> &Span {
+        match self {
+            TokenizeError::UnexpectedCharacter { span, .. }
+            | TokenizeError::UnterminatedString { span }
+            | TokenizeError::UnterminatedQuotedIdentifier { span }
+            | TokenizeError::UnterminatedComment { span }
+            | TokenizeError::MalformedNumber { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for TokenizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.span().start)?;
+        match self {
+            TokenizeError::UnexpectedCharacter { ch, .. } => {
+                write!(f, "Unexpected character '{}'", ch)
+            }
+            TokenizeError::UnterminatedString { .. } => write!(f, "Unterminated string literal"),
+            TokenizeError::UnterminatedQuotedIdentifier { .. } => {
+                write!(f, "Unterminated quoted identifier")
+            }
+            TokenizeError::UnterminatedComment { .. } => write!(f, "Unterminated block comment"),
+            TokenizeError::MalformedNumber { literal, .. } => {
+                write!(f, "Malformed number literal '{}'", literal)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TokenizeError {}
+
+pub fn tokenize(query: &str) -> Result<Vec<TokenWithSpan>, TokenizeError> {
+    let mut tokens: Vec<TokenWithSpan> = vec![];
+    let mut comments = vec![];
     let buff = StringBuf::new(query);
     let mut buff_iter = buff.iter();
 
     while let Some(ch) = buff_iter.next() {
+        let start = buff_iter.pos() - ch.len_utf8();
         let token = match ch {
             _ if ch.is_whitespace() => continue,
+            '-' | '/' if matches!((ch, buff_iter.curr()), ('-', Some('-')) | ('/', Some('*'))) => {
+                let comment = tokenize_comment(&mut buff_iter, ch, start)?;
+                if is_hint(&comment, &tokens, &comments) {
+                    Token::Hint(comment.text)
+                } else {
+                    // A comment sharing a line with the previous token trails it, any other
+                    // comment leads the next token.
+                    match tokens.last_mut() {
+                        Some(last) if last.span.end.line == comment.span.start.line => {
+                            last.trailing_comments.push(comment)
+                        }
+                        _ => comments.push(comment),
+                    }
+                    continue;
+                }
+            }
+            'n' | 'N' | 'q' | 'Q' if starts_quoted_literal(ch, &buff_iter) => {
+                tokenize_quoted_literal(&mut buff_iter, start)?
+            }
             'A'..='Z' | 'a'..='z' => tokenize_string(&mut buff_iter)?,
             '0'..='9' => tokenize_number(&mut buff_iter)?,
-            '\"' | '\'' => tokenize_quoted_literal(&mut buff_iter)?,
+            '\"' | '\'' => tokenize_quoted_literal(&mut buff_iter, start)?,
+            '*' if buff_iter.next_if_eq('*') => Token::DoubleAsterisk,
             '*' => Token::Asterisk('*'),
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '|' if buff_iter.next_if_eq('|') => Token::Concat,
+            '<' if buff_iter.next_if_eq('=') => Token::LtEq,
+            '<' if buff_iter.next_if_eq('>') => Token::Neq,
+            '<' => Token::Lt,
+            '>' if buff_iter.next_if_eq('=') => Token::GtEq,
+            '>' => Token::Gt,
+            '!' if buff_iter.next_if_eq('=') => Token::BangEq,
+            '^' if buff_iter.next_if_eq('=') => Token::CaretEq,
+            '~' if buff_iter.next_if_eq('=') => Token::TildeEq,
+            ':' if buff_iter.next_if_eq('=') => Token::Assignment,
+            ':' if buff_iter.curr().is_some_and(is_word_char) => {
+                tokenize_variable(&mut buff_iter, ch, start)?
+            }
             ':' => Token::Colon,
+            '&' => tokenize_variable(&mut buff_iter, ch, start)?,
             ';' => Token::Semicolon,
+            '.' if buff_iter.next_if_eq('.') => Token::DoubleDot,
+            '.' if buff_iter.curr().is_some_and(|ch| ch.is_ascii_digit()) => {
+                tokenize_number(&mut buff_iter)?
+            }
             '.' => Token::Dot,
             ',' => Token::Comma,
+            '=' if buff_iter.next_if_eq('>') => Token::Arrow,
             '=' => Token::Eq,
+            '@' => Token::AtSign,
             '(' => Token::LParen,
             ')' => Token::RParen,
-            _ => return Err(format!("Unexpected token '{}'", ch)),
+            _ => {
+                return Err(TokenizeError::UnexpectedCharacter {
+                    ch,
+                    span: buff_iter.span_from(start),
+                })
+            }
         };
-        tokens.push(token)
+        tokens.push(TokenWithSpan {
+            token,
+            span: buff.span(start..buff_iter.pos()),
+            leading_comments: std::mem::take(&mut comments),
+            trailing_comments: vec![],
+        })
     }
-    tokens.push(Token::EOF);
+    tokens.push(TokenWithSpan {
+        token: Token::EOF,
+        span: buff.span(query.len()..query.len()),
+        leading_comments: comments,
+        trailing_comments: vec![],
+    });
 
     Ok(tokens)
 }
 
-fn tokenize_string(buff_iter: &mut StringBufIterator) -> Result<Token, String> {
+fn tokenize_comment(
+    buff_iter: &mut StringBufIterator,
+    ch: char,
+    start: usize,
+) -> Result<Comment, TokenizeError> {
+    let kind = match (ch, buff_iter.curr()) {
+        ('-', Some('-')) => CommentKind::Line,
+        ('/', Some('*')) => CommentKind::Block,
+        _ => {
+            return Err(TokenizeError::UnexpectedCharacter {
+                ch,
+                span: buff_iter.span_from(start),
+            })
+        }
+    };
+    buff_iter.next();
+
+    match kind {
+        CommentKind::Line => {
+            buff_iter.fetch_while(|ch| ch != '\n' && ch != '\r');
+        }
+        CommentKind::Block => {
+            if buff_iter.fetch_to_delim("*/").is_none() {
+                return Err(TokenizeError::UnterminatedComment {
+                    span: buff_iter.span_from(start),
+                });
+            }
+        }
+    }
+    Ok(Comment {
+        kind,
+        text: buff_iter.slice_from(start).to_string(),
+        span: buff_iter.span_from(start),
+    })
+}
+
+/// Oracle only reads a hint right after the keyword that starts a statement or a query
+/// block; anywhere else, or with another comment in between, it is an ordinary comment.
+fn is_hint(comment: &Comment, tokens: &[TokenWithSpan], comments: &[Comment]) -> bool {
+    let hint_prefix = match comment.kind {
+        CommentKind::Line => "--+",
+        CommentKind::Block => "/*+",
+    };
+    comment.text.starts_with(hint_prefix)
+        && comments.is_empty()
+        && tokens.last().is_some_and(|last| {
+            last.trailing_comments.is_empty()
+                && matches!(
+                    last.token,
+                    Token::Keyword(
+                        Keyword::Select
+                            | Keyword::Insert
+                            | Keyword::Update
+                            | Keyword::Delete
+                            | Keyword::Merge
+                    )
+                )
+        })
+}
+
+fn is_word_char(ch: char) -> bool {
+    matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9' | '_')
+}
+
+// Unquoted names may also contain `$` and `#` after their first character, as in `v$session`.
+fn is_name_char(ch: char) -> bool {
+    is_word_char(ch) || matches!(ch, '$' | '#')
+}
+
+fn tokenize_string(buff_iter: &mut StringBufIterator) -> Result<Token, TokenizeError> {
     buff_iter.prev();
-    let word = buff_iter.fetch_while(|ch| matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9' | '_' ));
+    let word = buff_iter.fetch_while(is_name_char);
 
     if let Ok(keyword) = word.parse::<Keyword>() {
         return Ok(Token::Keyword(keyword));
     }
-    if let Ok(identifier) = word.parse::<String>() {
-        return Ok(Token::Identifier(identifier));
+    Ok(Token::Identifier(word.to_string()))
+}
+
+/// Reads a `:name` bind variable or a `&name` / `&&name` substitution variable.
+fn tokenize_variable(
+    buff_iter: &mut StringBufIterator,
+    ch: char,
+    start: usize,
+) -> Result<Token, TokenizeError> {
+    if ch == '&' {
+        buff_iter.next_if_eq('&');
+    }
+    if buff_iter.fetch_while(is_word_char).is_empty() {
+        return Err(TokenizeError::UnexpectedCharacter {
+            ch,
+            span: buff_iter.span_from(start),
+        });
+    }
+    if ch == ':' {
+        // Trigger pseudo records, `:new.col`.
+        while buff_iter.curr() == Some('.') && buff_iter.peek(1).is_some_and(is_word_char) {
+            buff_iter.next();
+            buff_iter.fetch_while(is_word_char);
+        }
+        return Ok(Token::BindVariable(buff_iter.slice_from(start).to_string()));
+    }
+    // SQL*Plus ends the name at a period and glues what follows to the value, so `&v.x` is one
+    // word and `&owner..tab` is `&owner.` followed by `.tab`.
+    if buff_iter.next_if_eq('.') {
+        buff_iter.fetch_while(is_word_char);
     }
-    Err(format!("Unexpected string token {}", word))
+    Ok(Token::SubstitutionVariable(
+        buff_iter.slice_from(start).to_string(),
+    ))
 }
 
-fn tokenize_number(buff_iter: &mut StringBufIterator) -> Result<Token, String> {
+/// Reads `[digits][.digits][e[+|-]digits][f|d]`, keeping the lexeme as written so the
+/// formatter never changes a value.
+fn tokenize_number(buff_iter: &mut StringBufIterator) -> Result<Token, TokenizeError> {
     buff_iter.prev();
-    let word = buff_iter.fetch_while(|ch| matches!(ch, '0'..='9' | '.'));
-
-    match word.parse::<f64>() {
-        Ok(w) => Ok(Token::NumberLiteral(w.to_string())),
-        Err(_) => Err(format!("Unexpected number token {}", word)),
+    let start = buff_iter.pos();
+    buff_iter.fetch_while(|ch| ch.is_ascii_digit());
+    // `1..10` is a range, not a number.
+    if buff_iter.curr() == Some('.') && buff_iter.peek(1) != Some('.') {
+        buff_iter.next();
+        buff_iter.fetch_while(|ch| ch.is_ascii_digit());
+    }
+    // Without digits after it the `e` starts the next word, as in `select 1e from dual`.
+    let exponent = match (buff_iter.peek(0), buff_iter.peek(1), buff_iter.peek(2)) {
+        (Some('e' | 'E'), Some('+' | '-'), Some(digit)) if digit.is_ascii_digit() => 2,
+        (Some('e' | 'E'), Some(digit), _) if digit.is_ascii_digit() => 1,
+        _ => 0,
+    };
+    if exponent > 0 {
+        for _ in 0..exponent {
+            buff_iter.next();
+        }
+        buff_iter.fetch_while(|ch| ch.is_ascii_digit());
+    }
+    // BINARY_FLOAT and BINARY_DOUBLE literals.
+    buff_iter.next_if(|ch| matches!(ch, 'f' | 'F' | 'd' | 'D'));
+
+    if buff_iter.curr() == Some('.') && buff_iter.peek(1) != Some('.') {
+        buff_iter.fetch_while(|ch| ch.is_ascii_digit() || ch == '.');
+        return Err(TokenizeError::MalformedNumber {
+            literal: buff_iter.slice_from(start).to_string(),
+            span: buff_iter.span_from(start),
+        });
     }
+    Ok(Token::NumberLiteral(
+        buff_iter.slice_from(start).to_string(),
+    ))
 }
 
-fn tokenize_quoted_literal(buff_iter: &mut StringBufIterator) -> Result<Token, String> {
-    let mut string_literal = String::new();
+/// Reads a `"quoted"` identifier or a string literal, including the `N'...'`, `Q'[...]'` and
+/// `NQ'[...]'` forms. Both keep their source text, quotes and prefix included.
+fn tokenize_quoted_literal(
+    buff_iter: &mut StringBufIterator,
+    start: usize,
+) -> Result<Token, TokenizeError> {
+    buff_iter.prev();
+    buff_iter.next_if(|ch| matches!(ch, 'n' | 'N'));
+    let alternative = buff_iter.next_if(|ch| matches!(ch, 'q' | 'Q')).is_some();
 
-    match buff_iter.prev() {
+    let terminated = match buff_iter.next() {
         Some('\"') => {
-            buff_iter.next();
-            if let Some(word) = buff_iter.fetch_to_delim("\"") {
-                string_literal.push_str(word)
+            if !fetch_quoted(buff_iter, '"') {
+                return Err(TokenizeError::UnterminatedQuotedIdentifier {
+                    span: buff_iter.span_from(start),
+                });
             }
-            Ok(Token::Identifier(string_literal))
+            return Ok(Token::QuotedIdentifier(
+                buff_iter.slice_from(start).to_string(),
+            ));
         }
-        Some('\'') => {
-            buff_iter.next();
-            if let Some(word) = buff_iter.fetch_to_delim("\'") {
-                string_literal.push_str(word)
-            }
-            Ok(Token::StringLiteral(string_literal))
+        // q'[...]' ends at the closing bracket followed by a quote, any other delimiter
+        // closes itself.
+        Some('\'') if alternative => {
+            let close = match buff_iter.next() {
+                Some('[') => ']',
+                Some('{') => '}',
+                Some('(') => ')',
+                Some('<') => '>',
+                Some(ch) if !ch.is_whitespace() => ch,
+                _ => {
+                    return Err(TokenizeError::UnterminatedString {
+                        span: buff_iter.span_from(start),
+                    })
+                }
+            };
+            buff_iter.fetch_to_delim(&format!("{}'", close)).is_some()
         }
+        Some('\'') => fetch_quoted(buff_iter, '\''),
+        ch => {
+            return Err(TokenizeError::UnexpectedCharacter {
+                ch: ch.unwrap_or_default(),
+                span: buff_iter.span_from(start),
+            })
+        }
+    };
+    if !terminated {
+        return Err(TokenizeError::UnterminatedString {
+            span: buff_iter.span_from(start),
+        });
+    }
+    Ok(Token::StringLiteral(
+        buff_iter.slice_from(start).to_string(),
+    ))
+}
 
-        _ => Err("Oops".to_string()),
+/// Reads up to and including the closing `quote`, a doubled quote stands for the quote itself.
+/// Returns false when the input ends first.
+fn fetch_quoted(buff_iter: &mut StringBufIterator, quote: char) -> bool {
+    loop {
+        buff_iter.fetch_while(|ch| ch != quote);
+        if buff_iter.next().is_none() {
+            return false;
+        }
+        if !buff_iter.next_if_eq(quote) {
+            return true;
+        }
     }
 }
 
+/// `N'`, `Q'` and `NQ'` open a string literal, otherwise these letters start a word.
+fn starts_quoted_literal(ch: char, buff_iter: &StringBufIterator) -> bool {
+    matches!(
+        (ch, buff_iter.peek(0), buff_iter.peek(1)),
+        (_, Some('\''), _) | ('n' | 'N', Some('q' | 'Q'), Some('\''))
+    )
+}
+
 #[cfg(test)]
 mod test {
     use crate::tokenizer::keyword::Keyword;
-    use crate::tokenizer::{tokenize, Token};
+    use crate::tokenizer::{tokenize, Comment, CommentKind, Location, Token, TokenizeError};
+
+    fn tokenize_tokens(query: &str) -> Result<Vec<Token>, TokenizeError> {
+        tokenize(query).map(|tokens| tokens.into_iter().map(|t| t.token).collect())
+    }
+
+    #[test]
+    pub fn test_tokenize_errors() {
+        let error = tokenize("select a\n from b ? c").unwrap_err();
+        assert!(matches!(
+            error,
+            TokenizeError::UnexpectedCharacter { ch: '?', .. }
+        ));
+        assert_eq!(17..18, error.span().range);
+        assert_eq!("2:9: Unexpected character '?'", error.to_string());
+
+        let error = tokenize("select 'abc from dual").unwrap_err();
+        assert!(matches!(error, TokenizeError::UnterminatedString { .. }));
+        assert_eq!(7..21, error.span().range);
+
+        let error = tokenize("select \"abc from dual").unwrap_err();
+        assert!(matches!(
+            error,
+            TokenizeError::UnterminatedQuotedIdentifier { .. }
+        ));
+
+        let error = tokenize("select 1.2.3 from dual").unwrap_err();
+        assert_eq!(
+            TokenizeError::MalformedNumber {
+                literal: "1.2.3".to_string(),
+                span: error.span().clone(),
+            },
+            error
+        );
+        assert_eq!(7..12, error.span().range);
+    }
+
+    #[test]
+    pub fn test_comments_attach_to_tokens() {
+        let query = "-- head\nselect a, /* one */ -- two\n/* three\n */ b -- four";
+
+        let tokens = tokenize(query).unwrap();
+        let texts = |comments: &Vec<Comment>| -> Vec<String> {
+            comments.iter().map(|c| c.text.clone()).collect()
+        };
+        assert_eq!(Token::Keyword(Keyword::Select), tokens[0].token);
+        assert_eq!(vec!["-- head"], texts(&tokens[0].leading_comments));
+        assert_eq!(Token::Comma, tokens[2].token);
+        assert_eq!(
+            vec!["/* one */", "-- two"],
+            texts(&tokens[2].trailing_comments)
+        );
+        assert_eq!(vec!["/* three\n */"], texts(&tokens[3].leading_comments));
+        assert_eq!(vec!["-- four"], texts(&tokens[3].trailing_comments));
+        assert_eq!(CommentKind::Block, tokens[3].leading_comments[0].kind);
+        assert_eq!(Token::EOF, tokens[4].token);
+        assert!(tokens[4].leading_comments.is_empty());
+
+        let error = tokenize("select a /* b").unwrap_err();
+        assert!(matches!(error, TokenizeError::UnterminatedComment { .. }));
+    }
+
+    #[test]
+    pub fn test_hints() {
+        let query = "select /*+ INDEX(t i) */ a, /*+ not a hint */ b from t";
+
+        let tokens = tokenize(query).unwrap();
+        assert_eq!(
+            Token::Hint("/*+ INDEX(t i) */".to_string()),
+            tokens[1].token
+        );
+        assert_eq!(7..24, tokens[1].span.range);
+        assert_eq!(Token::Comma, tokens[3].token);
+        assert_eq!("/*+ not a hint */", tokens[3].trailing_comments[0].text);
+
+        let tokens = tokenize_tokens("select --+ PARALLEL\n a from t").unwrap();
+        assert_eq!(Token::Hint("--+ PARALLEL".to_string()), tokens[1]);
+
+        let tokens = tokenize("select /* c */ /*+ FULL(t) */ a from t").unwrap();
+        assert_eq!(Token::Identifier("a".to_string()), tokens[1].token);
+        assert_eq!(2, tokens[0].trailing_comments.len());
+
+        let tokens = tokenize_tokens("delete /*+ FULL(t) */ from t").unwrap();
+        assert_eq!(Token::Hint("/*+ FULL(t) */".to_string()), tokens[1]);
+    }
+
+    #[test]
+    pub fn test_operators() {
+        let query = "a+b-c/d%e||f**2*g <> != ^= ~= < <= > >= = := => @ 1..10";
+
+        let tokens = tokenize_tokens(query).unwrap();
+        let ident = |name: &str| Token::Identifier(name.to_string());
+        assert_eq!(
+            vec![
+                ident("a"),
+                Token::Plus,
+                ident("b"),
+                Token::Minus,
+                ident("c"),
+                Token::Slash,
+                ident("d"),
+                Token::Percent,
+                ident("e"),
+                Token::Concat,
+                ident("f"),
+                Token::DoubleAsterisk,
+                Token::NumberLiteral("2".to_string()),
+                Token::Asterisk('*'),
+                ident("g"),
+                Token::Neq,
+                Token::BangEq,
+                Token::CaretEq,
+                Token::TildeEq,
+                Token::Lt,
+                Token::LtEq,
+                Token::Gt,
+                Token::GtEq,
+                Token::Eq,
+                Token::Assignment,
+                Token::Arrow,
+                Token::AtSign,
+                Token::NumberLiteral("1".to_string()),
+                Token::DoubleDot,
+                Token::NumberLiteral("10".to_string()),
+                Token::EOF,
+            ],
+            tokens
+        );
+
+        assert!(matches!(
+            tokenize("a | b"),
+            Err(TokenizeError::UnexpectedCharacter { ch: '|', .. })
+        ));
+        assert!(matches!(
+            tokenize("a ! b"),
+            Err(TokenizeError::UnexpectedCharacter { ch: '!', .. })
+        ));
+    }
+
+    #[test]
+    pub fn test_token_spans() {
+        let query = "select a,\n  \"b\" from kek;";
+
+        let tokens = tokenize(query).unwrap();
+        let spans: Vec<(usize, usize, Location)> = tokens
+            .iter()
+            .map(|t| (t.span.range.start, t.span.range.end, t.span.start))
+            .collect();
+        let at = |line, column| Location { line, column };
+        assert_eq!(
+            vec![
+                (0, 6, at(1, 1)),
+                (7, 8, at(1, 8)),
+                (8, 9, at(1, 9)),
+                (12, 15, at(2, 3)),
+                (16, 20, at(2, 7)),
+                (21, 24, at(2, 12)),
+                (24, 25, at(2, 15)),
+                (25, 25, at(2, 16)),
+            ],
+            spans
+        );
+        assert_eq!(at(2, 6), tokens[3].span.end);
+    }
 
     #[test]
     pub fn test_simple_query() {
         let query = "select * from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
@@ -122,6 +661,7 @@ mod test {
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -134,7 +674,7 @@ mod test {
     pub fn test_complex_identifier() {
         let query = "select maf_123 from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
@@ -142,6 +682,7 @@ mod test {
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -150,11 +691,30 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn test_dollar_and_hash_in_names() {
+        assert_eq!(
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::Identifier(String::from("a#1")),
+                Token::Keyword(Keyword::From),
+                Token::Identifier(String::from("gv$instance")),
+                Token::Semicolon,
+                Token::EOF,
+            ],
+            tokenize_tokens("select a#1 from gv$instance;").unwrap()
+        );
+        assert!(matches!(
+            tokenize_tokens("select $a from dual"),
+            Err(TokenizeError::UnexpectedCharacter { ch: '$', .. })
+        ));
+    }
+
     #[test]
     pub fn test_alias_query() {
         let query = "select mem as lol from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
@@ -164,6 +724,7 @@ mod test {
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -176,7 +737,7 @@ mod test {
     pub fn test_f64_num_query() {
         let query = "select 123.321 lol from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
@@ -185,6 +746,7 @@ mod test {
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -197,7 +759,7 @@ mod test {
     pub fn test_i32_num_query() {
         let query = "select 123 lol from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
@@ -206,6 +768,7 @@ mod test {
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -217,7 +780,7 @@ mod test {
     pub fn test_join_query() {
         let query = "select 123, mem from kek join lol on kek.id=lol.id;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
@@ -237,6 +800,7 @@ mod test {
                     Token::Dot,
                     Token::Identifier(String::from("id")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -249,16 +813,17 @@ mod test {
     pub fn test_string_literal_query() {
         let query = "select \'123\', \'mem\' from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
-                    Token::StringLiteral("123".to_string()),
+                    Token::StringLiteral("'123'".to_string()),
                     Token::Comma,
-                    Token::StringLiteral(String::from("mem")),
+                    Token::StringLiteral(String::from("'mem'")),
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -267,20 +832,126 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn test_alternative_and_national_literals() {
+        let query = "select q'[it's]', Nq'{a'b}', n'текст', Q'!x]!', q'<(a)>' from qq;";
+
+        assert_eq!(
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::StringLiteral("q'[it's]'".to_string()),
+                Token::Comma,
+                Token::StringLiteral("Nq'{a'b}'".to_string()),
+                Token::Comma,
+                Token::StringLiteral("n'текст'".to_string()),
+                Token::Comma,
+                Token::StringLiteral("Q'!x]!'".to_string()),
+                Token::Comma,
+                Token::StringLiteral("q'<(a)>'".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("qq".to_string()),
+                Token::Semicolon,
+                Token::EOF,
+            ],
+            tokenize_tokens(query).unwrap()
+        );
+
+        let error = tokenize("select q'[a]").unwrap_err();
+        assert!(matches!(error, TokenizeError::UnterminatedString { .. }));
+        assert_eq!(7..12, error.span().range);
+        assert!(tokenize("select q' a '").is_err());
+    }
+
+    #[test]
+    pub fn test_numbers_keep_their_lexeme() {
+        let query = "007 1.50 .5 1. 1e10 1.5E-3 2.0f 10D 3d 1e x 1..2";
+
+        assert_eq!(
+            vec![
+                Token::NumberLiteral("007".to_string()),
+                Token::NumberLiteral("1.50".to_string()),
+                Token::NumberLiteral(".5".to_string()),
+                Token::NumberLiteral("1.".to_string()),
+                Token::NumberLiteral("1e10".to_string()),
+                Token::NumberLiteral("1.5E-3".to_string()),
+                Token::NumberLiteral("2.0f".to_string()),
+                Token::NumberLiteral("10D".to_string()),
+                Token::NumberLiteral("3d".to_string()),
+                Token::NumberLiteral("1".to_string()),
+                Token::Identifier("e".to_string()),
+                Token::Identifier("x".to_string()),
+                Token::NumberLiteral("1".to_string()),
+                Token::DoubleDot,
+                Token::NumberLiteral("2".to_string()),
+                Token::EOF,
+            ],
+            tokenize_tokens(query).unwrap()
+        );
+
+        let error = tokenize("select .5.5").unwrap_err();
+        assert!(matches!(error, TokenizeError::MalformedNumber { .. }));
+        assert_eq!(7..11, error.span().range);
+    }
+
+    #[test]
+    pub fn test_variables() {
+        let query = "select :id, :1, :new.col, &var, &&var, &owner..tab, &v.x from t where a := :b";
+
+        assert_eq!(
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::BindVariable(":id".to_string()),
+                Token::Comma,
+                Token::BindVariable(":1".to_string()),
+                Token::Comma,
+                Token::BindVariable(":new.col".to_string()),
+                Token::Comma,
+                Token::SubstitutionVariable("&var".to_string()),
+                Token::Comma,
+                Token::SubstitutionVariable("&&var".to_string()),
+                Token::Comma,
+                Token::SubstitutionVariable("&owner.".to_string()),
+                Token::Dot,
+                Token::Identifier("tab".to_string()),
+                Token::Comma,
+                Token::SubstitutionVariable("&v.x".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("t".to_string()),
+                Token::Keyword(Keyword::Where),
+                Token::Identifier("a".to_string()),
+                Token::Assignment,
+                Token::BindVariable(":b".to_string()),
+                Token::EOF,
+            ],
+            tokenize_tokens(query).unwrap()
+        );
+
+        let error = tokenize("select & from t").unwrap_err();
+        assert!(matches!(
+            error,
+            TokenizeError::UnexpectedCharacter { ch: '&', .. }
+        ));
+        assert_eq!(
+            vec![Token::Colon, Token::EOF],
+            tokenize_tokens(":").unwrap()
+        );
+    }
+
     #[test]
     pub fn test_quoted_identifier_query() {
         let query = "select \'123\', \"mem\" from kek;\n";
 
-        if let Ok(tokens) = tokenize(&query) {
+        if let Ok(tokens) = tokenize_tokens(query) {
             assert_eq!(
                 vec![
                     Token::Keyword(Keyword::Select),
-                    Token::StringLiteral("123".to_string()),
+                    Token::StringLiteral("'123'".to_string()),
                     Token::Comma,
-                    Token::Identifier(String::from("mem")),
+                    Token::QuotedIdentifier(String::from("\"mem\"")),
                     Token::Keyword(Keyword::From),
                     Token::Identifier(String::from("kek")),
                     Token::Semicolon,
+                    Token::EOF,
                 ],
                 tokens
             );
@@ -288,4 +959,31 @@ mod test {
             assert_eq!(1, 0);
         }
     }
+
+    #[test]
+    pub fn test_doubled_quotes() {
+        let query = "select 'O''Brien', '''', \"a\"\"b\", '' from \"T\"\"\";";
+
+        assert_eq!(
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::StringLiteral("'O''Brien'".to_string()),
+                Token::Comma,
+                Token::StringLiteral("''''".to_string()),
+                Token::Comma,
+                Token::QuotedIdentifier("\"a\"\"b\"".to_string()),
+                Token::Comma,
+                Token::StringLiteral("''".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::QuotedIdentifier("\"T\"\"\"".to_string()),
+                Token::Semicolon,
+                Token::EOF,
+            ],
+            tokenize_tokens(query).unwrap()
+        );
+
+        let error = tokenize("select 'it''s").unwrap_err();
+        assert!(matches!(error, TokenizeError::UnterminatedString { .. }));
+        assert_eq!(7..13, error.span().range);
+    }
 }
@@ -1,21 +1,43 @@
+use std::ops::Range;
+
+use super::{Location, Span};
+
 pub struct StringBuf<'a> {
     buf: &'a str,
+    // Byte offsets at which every line begins, used to turn positions into locations.
+    line_starts: Vec<usize>,
 }
 
 impl<'a> StringBuf<'a> {
-    pub fn new(buf: &str) -> StringBuf {
-        StringBuf { buf }
+    pub fn new(buf: &str) -> StringBuf<'_> {
+        let line_starts = std::iter::once(0)
+            .chain(buf.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+        StringBuf { buf, line_starts }
+    }
+    pub fn location(&self, pos: usize) -> Location {
+        let line = self.line_starts.partition_point(|&start| start <= pos) - 1;
+        let column = self.buf[self.line_starts[line]..pos].chars().count() + 1;
+        Location {
+            line: line + 1,
+            column,
+        }
     }
-    pub fn buf(&self) -> &'a str {
-        self.buf
+    pub fn span(&self, range: Range<usize>) -> Span {
+        Span {
+            start: self.location(range.start),
+            end: self.location(range.end),
+            range,
+        }
     }
-    pub fn iter(&self) -> StringBufIterator {
+    pub fn iter(&self) -> StringBufIterator<'_> {
         StringBufIterator {
             string_buf: self,
             pos: 0,
         }
     }
-    pub fn iter_from(&'a self, pos: usize) -> StringBufIterator {
+    #[cfg(test)]
+    pub fn iter_from(&'a self, pos: usize) -> StringBufIterator<'a> {
         if pos > self.buf.len() {
             panic!("Iter position index out of bounds")
         }
@@ -33,11 +55,51 @@ pub struct StringBufIterator<'a> {
 
 impl<'a> StringBufIterator<'a> {
     pub fn prev(&mut self) -> Option<char> {
-        let ch = self.string_buf.buf[..self.pos].chars().rev().next();
-        self.pos -= 1;
+        let ch = self.string_buf.buf[..self.pos].chars().next_back();
+        if let Some(ch) = ch {
+            self.pos -= ch.len_utf8();
+        }
         ch
     }
 
+    /// Looks `n` characters ahead without moving, `peek(0)` is the current one.
+    pub fn peek(&self, n: usize) -> Option<char> {
+        self.string_buf.buf[self.pos..].chars().nth(n)
+    }
+
+    /// Consumes the current character if it is `expected`.
+    pub fn next_if_eq(&mut self, expected: char) -> bool {
+        if self.curr() == Some(expected) {
+            self.next();
+            return true;
+        }
+        false
+    }
+
+    /// Consumes the current character if `check_func` accepts it.
+    pub fn next_if<F>(&mut self, check_func: F) -> Option<char>
+    where
+        F: FnOnce(char) -> bool,
+    {
+        let ch = self.curr().filter(|&ch| check_func(ch))?;
+        self.next();
+        Some(ch)
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Source text from `start` up to the current position.
+    pub fn slice_from(&self, start: usize) -> &'a str {
+        &self.string_buf.buf[start..self.pos]
+    }
+
+    /// Span from `start` up to the current position.
+    pub fn span_from(&self, start: usize) -> Span {
+        self.string_buf.span(start..self.pos)
+    }
+
     pub fn curr(&self) -> Option<char> {
         self.string_buf.buf[self.pos..].chars().next()
     }
@@ -84,12 +146,13 @@ impl<'a> Iterator for StringBufIterator<'a> {
 mod test {
 
     use super::StringBuf;
+    use crate::tokenizer::Location;
 
     #[test]
     pub fn test_normal_buff_iter() {
         let query = "select * from kek;\n";
 
-        let buff = StringBuf::new(&query);
+        let buff = StringBuf::new(query);
 
         let result = buff.iter().next();
         assert_eq!(Some('s'), result);
@@ -142,7 +205,7 @@ mod test {
     pub fn test_empty_buff_iter() {
         let query = "";
 
-        let buff = StringBuf::new(&query);
+        let buff = StringBuf::new(query);
 
         let result = buff.iter().next();
         assert_eq!(None, result);
@@ -154,12 +217,34 @@ mod test {
         assert_eq!(None, result);
     }
 
+    #[test]
+    pub fn test_location() {
+        let query = "select\n  фыва,\r\nb";
+
+        let buff = StringBuf::new(query);
+
+        assert_eq!((1, 1), loc(buff.location(0)));
+        assert_eq!((1, 7), loc(buff.location(6)));
+        assert_eq!((2, 3), loc(buff.location(9)));
+        // Columns count characters, not bytes.
+        assert_eq!((2, 7), loc(buff.location(17)));
+        assert_eq!((3, 2), loc(buff.location(query.len())));
+
+        let mut buff_iter = buff.iter_from(17);
+        assert_eq!(Some('а'), buff_iter.prev());
+        assert_eq!(15, buff_iter.pos());
+    }
+
+    fn loc(location: Location) -> (usize, usize) {
+        (location.line, location.column)
+    }
+
     #[test]
     #[should_panic(expected = "Iter position index out of bounds")]
     pub fn test_empty_buff_iter_at_panic() {
         let query = "";
 
-        let buff = StringBuf::new(&query);
+        let buff = StringBuf::new(query);
 
         let _ = buff.iter_from(7).curr();
     }